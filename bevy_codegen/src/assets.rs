@@ -0,0 +1,223 @@
+use std::{fmt::Display, path::Path};
+
+use syn::{
+    visit::{self, Visit},
+    Block, Expr, ExprMethodCall, File, Lit,
+};
+
+use crate::model::{BevyModel, Custom, Resource};
+
+pub const ASSETS_RESOURCE_NAME: &str = "GameAssets";
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct MissingAsset {
+    pub path: String,
+    // Asset or system referring to the path
+    pub source: String,
+}
+
+impl Display for MissingAsset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "`{}` used by `{}` does not exist",
+            self.path, self.source
+        )
+    }
+}
+
+impl BevyModel {
+    pub fn assets_resource(&self) -> Option<Resource> {
+        if self.assets.is_empty() {
+            return None;
+        }
+        Some(Resource {
+            name: ASSETS_RESOURCE_NAME.to_string(),
+            content: self
+                .assets
+                .iter()
+                .map(|a| (a.name.clone(), format!("Handle<{}>", a.kind.ty())))
+                .collect(),
+            is_reflected: false,
+            ..Default::default()
+        })
+    }
+
+    // Body of `FromWorld for GameAssets`, so the handles exist before any startup system runs
+    pub fn load_assets_content(&self) -> Option<String> {
+        if self.assets.is_empty() {
+            return None;
+        }
+        let handles = self
+            .assets
+            .iter()
+            .map(|a| format!("{}: asset_server.load(\"{}\"),", a.name, a.path))
+            .collect::<Vec<String>>()
+            .join("\n");
+        Some(format!(
+            "let asset_server = world.resource::<AssetServer>();\n{ASSETS_RESOURCE_NAME} {{\n{handles}\n}}"
+        ))
+    }
+
+    // Manifest entries and `asset_server.load("...")` literals not found in `Meta.asset_path`
+    pub fn missing_assets(&self) -> Vec<MissingAsset> {
+        let mut used = vec![];
+        for asset in &self.assets {
+            used.push((asset.path.clone(), asset.name.clone()));
+        }
+        for system in self.startup_systems.iter().chain(self.systems.iter()) {
+            for path in load_literals(&system.content) {
+                used.push((path, system.name.clone()));
+            }
+        }
+        for custom in &self.custom {
+            let (Custom::Main(code) | Custom::Component(code) | Custom::System(code)) = custom;
+            for path in load_literals(&code.content) {
+                used.push((path, code.name.clone()));
+            }
+        }
+
+        let asset_folder = Path::new(&self.meta.asset_path);
+        used.into_iter()
+            .filter(|(path, _)| {
+                // Labels like `#Scene0` point into the file
+                let file = path.split('#').next().unwrap_or_default();
+                !asset_folder.join(file).is_file()
+            })
+            .map(|(path, source)| MissingAsset { path, source })
+            .collect()
+    }
+}
+
+// String literals passed to `.load(..)`, code that doesn't parse is skipped
+fn load_literals(content: &str) -> Vec<String> {
+    let mut visitor = LoadVisitor::default();
+    if let Ok(block) = syn::parse_str::<Block>(&format!("{{{content}}}")) {
+        visitor.visit_block(&block);
+    } else if let Ok(file) = syn::parse_str::<File>(content) {
+        visitor.visit_file(&file);
+    }
+    visitor.literals
+}
+
+#[derive(Default)]
+struct LoadVisitor {
+    literals: Vec<String>,
+}
+
+impl<'ast> Visit<'ast> for LoadVisitor {
+    fn visit_expr_method_call(&mut self, call: &'ast ExprMethodCall) {
+        if call.method == "load" {
+            if let Some(Expr::Lit(lit)) = call.args.first() {
+                if let Lit::Str(path) = &lit.lit {
+                    self.literals.push(path.value());
+                }
+            }
+        }
+        visit::visit_expr_method_call(self, call);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{self, File};
+
+    use crate::model::{Asset, AssetKind, Meta, System};
+
+    use super::*;
+
+    fn model(asset_path: &str) -> BevyModel {
+        BevyModel {
+            meta: Meta {
+                asset_path: asset_path.to_string(),
+                ..Default::default()
+            },
+            assets: vec![
+                Asset {
+                    name: "ship".to_string(),
+                    path: "ship_C.png".to_string(),
+                    kind: AssetKind::Image,
+                },
+                Asset {
+                    name: "music".to_string(),
+                    path: "music.ogg".to_string(),
+                    kind: AssetKind::Audio,
+                },
+            ],
+            systems: vec![System {
+                name: "spawn_enemy".to_string(),
+                content: "let a = asset_server.load(\"enemy_A.png\");\nlet b = asset_server.load(\"ship_C.png\");".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn assets_generate_handles_resource() {
+        let resource = model("assets")
+            .assets_resource()
+            .expect("Assets should exist");
+        assert_eq!(ASSETS_RESOURCE_NAME, resource.name);
+        assert_eq!(
+            vec![
+                ("ship".to_string(), "Handle<Image>".to_string()),
+                ("music".to_string(), "Handle<AudioSource>".to_string()),
+            ],
+            resource.content
+        );
+    }
+
+    #[test]
+    fn assets_generate_from_world() {
+        let content = model("assets")
+            .load_assets_content()
+            .expect("Assets should load");
+        assert_eq!(
+            content,
+            r#"let asset_server = world.resource::<AssetServer>();
+GameAssets {
+ship: asset_server.load("ship_C.png"),
+music: asset_server.load("music.ogg"),
+}"#
+        );
+    }
+
+    #[test]
+    fn load_literals_are_parsed() {
+        let content = r##"
+            // let old = asset_server.load("commented.png");
+            let a = asset_server.load( "spaced.png");
+            let b = asset_server.load(r#"raw.png"#);
+            let c = server.load::<Image>("turbofish.png");
+            let d = asset_server.load(path);
+        "##;
+        assert_eq!(
+            vec!["spaced.png", "raw.png", "turbofish.png"],
+            load_literals(content)
+        );
+    }
+
+    #[test]
+    fn missing_assets_are_reported() {
+        let folder = std::env::temp_dir().join("potoo_missing_assets_are_reported");
+        let _ = fs::create_dir_all(&folder);
+        File::create(folder.join("ship_C.png")).unwrap();
+
+        let missing = model(folder.to_str().unwrap()).missing_assets();
+        assert_eq!(
+            vec![
+                MissingAsset {
+                    path: "music.ogg".to_string(),
+                    source: "music".to_string(),
+                },
+                MissingAsset {
+                    path: "enemy_A.png".to_string(),
+                    source: "spawn_enemy".to_string(),
+                },
+            ],
+            missing
+        );
+        let _ = fs::remove_dir_all(folder);
+    }
+}
@@ -0,0 +1,149 @@
+use crate::model::{BevyVersion, Plugin, StatePlacement, System, SystemSchedule};
+
+// App builder calls and derives that differ between Bevy versions, `modifiers` are the
+// version independent `.in_set(..)`, `.before(..)`, `.after(..)` and `.run_if(..)` calls
+pub trait AppBackend {
+    fn add_plugin(&self, plugin: &Plugin) -> String;
+
+    fn add_startup_system(&self, system: &System, modifiers: &str) -> String;
+
+    fn add_system(&self, system: &System, modifiers: &str) -> String;
+
+    // `startup` and `systems` are the members of the set
+    fn configure_set(&self, config: &str, startup: &[&System], systems: &[&System]) -> String;
+
+    fn event_derives(&self) -> &[&str];
+
+    fn custom_asset_derives(&self) -> &[&str];
+}
+
+impl BevyVersion {
+    pub fn backend(&self) -> Box<dyn AppBackend> {
+        match self {
+            BevyVersion::V0_10 => Box::new(Bevy010),
+            BevyVersion::V0_11 => Box::new(Bevy011),
+        }
+    }
+}
+
+pub struct Bevy010;
+
+impl Bevy010 {
+    fn placement(system: &System) -> String {
+        let mut placement = "".to_owned();
+        match &system.state {
+            Some(StatePlacement::OnEnter(s)) => {
+                placement.push_str(&format!(".in_schedule(OnEnter({s}))"))
+            }
+            Some(StatePlacement::OnExit(s)) => {
+                placement.push_str(&format!(".in_schedule(OnExit({s}))"))
+            }
+            Some(StatePlacement::OnUpdate(s)) => {
+                placement.push_str(&format!(".in_set(OnUpdate({s}))"))
+            }
+            None => (),
+        }
+        match &system.schedule {
+            SystemSchedule::Update => (),
+            SystemSchedule::FixedUpdate => {
+                placement.push_str(".in_schedule(CoreSchedule::FixedUpdate)")
+            }
+            base_set => placement.push_str(&format!(".in_base_set(CoreSet::{base_set:?})")),
+        }
+        placement
+    }
+}
+
+impl AppBackend for Bevy010 {
+    fn add_plugin(&self, plugin: &Plugin) -> String {
+        match plugin.is_group {
+            true => format!(".add_plugins({})", plugin.app_config()),
+            false => format!(".add_plugin({})", plugin.name),
+        }
+    }
+
+    fn add_startup_system(&self, system: &System, modifiers: &str) -> String {
+        let placement = Bevy010::placement(system);
+        format!(
+            ".add_startup_system(systems::{}{placement}{modifiers})",
+            system.name
+        )
+    }
+
+    fn add_system(&self, system: &System, modifiers: &str) -> String {
+        let placement = Bevy010::placement(system);
+        format!(".add_system({}{placement}{modifiers})", system.name)
+    }
+
+    fn configure_set(&self, config: &str, _startup: &[&System], _systems: &[&System]) -> String {
+        format!(".configure_set({config})")
+    }
+
+    fn event_derives(&self) -> &[&str] {
+        &[]
+    }
+
+    fn custom_asset_derives(&self) -> &[&str] {
+        &[]
+    }
+}
+
+// Schedules are passed to `add_systems`, `OnUpdate` became a `in_state` run condition
+pub struct Bevy011;
+
+impl Bevy011 {
+    fn schedule(system: &System) -> String {
+        match &system.state {
+            Some(StatePlacement::OnEnter(s)) => format!("OnEnter({s})"),
+            Some(StatePlacement::OnExit(s)) => format!("OnExit({s})"),
+            _ => format!("{:?}", system.schedule),
+        }
+    }
+}
+
+impl AppBackend for Bevy011 {
+    fn add_plugin(&self, plugin: &Plugin) -> String {
+        format!(".add_plugins({})", plugin.app_config())
+    }
+
+    fn add_startup_system(&self, system: &System, modifiers: &str) -> String {
+        format!(".add_systems(Startup, systems::{}{modifiers})", system.name)
+    }
+
+    fn add_system(&self, system: &System, modifiers: &str) -> String {
+        let mut config = format!("{}{modifiers}", system.name);
+        if let Some(StatePlacement::OnUpdate(s)) = &system.state {
+            config.push_str(&format!(".run_if(in_state({s}))"));
+        }
+        format!(".add_systems({}, {config})", Bevy011::schedule(system))
+    }
+
+    // Sets are configured per schedule, once for every schedule one of their members runs in
+    fn configure_set(&self, config: &str, startup: &[&System], systems: &[&System]) -> String {
+        let mut schedules = vec![];
+        if !startup.is_empty() {
+            schedules.push("Startup".to_string());
+        }
+        for schedule in systems.iter().map(|s| Bevy011::schedule(s)) {
+            if !schedules.contains(&schedule) {
+                schedules.push(schedule);
+            }
+        }
+        if schedules.is_empty() {
+            schedules.push(format!("{:?}", SystemSchedule::Update));
+        }
+        schedules
+            .iter()
+            .map(|schedule| format!(".configure_set({schedule}, {config})"))
+            .collect()
+    }
+
+    fn event_derives(&self) -> &[&str] {
+        &["Event"]
+    }
+
+    // `add_asset` requires `TypePath` next to `TypeUuid`
+    fn custom_asset_derives(&self) -> &[&str] {
+        &["bevy::reflect::TypePath"]
+    }
+}
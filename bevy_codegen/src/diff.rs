@@ -0,0 +1,357 @@
+use std::fmt::Display;
+
+use crate::model::{BevyModel, BevyVersion, Component, Feature, Identified, System};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ElementKind {
+    Component,
+    Bundle,
+    Resource,
+    StartupSystem,
+    System,
+    Plugin,
+    Custom,
+}
+
+impl Display for ElementKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self {
+            ElementKind::Component => "component",
+            ElementKind::Bundle => "bundle",
+            ElementKind::Resource => "resource",
+            ElementKind::StartupSystem => "startup system",
+            ElementKind::System => "system",
+            ElementKind::Plugin => "plugin",
+            ElementKind::Custom => "custom code",
+        };
+        write!(f, "{kind}")
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Change {
+    Added(ElementKind, String),
+    Removed(ElementKind, String),
+    Renamed(ElementKind, String, String),
+    // Anything not covered by a more specific change below
+    Changed(ElementKind, String),
+    ComponentFieldAdded { component: String, field: String },
+    ComponentFieldRemoved { component: String, field: String },
+    ComponentFieldChanged { component: String, field: String },
+    SystemContentChanged(ElementKind, String),
+    FeatureEnabled(Feature),
+    FeatureDisabled(Feature),
+    BevyVersionChanged(BevyVersion, BevyVersion),
+    // Model fields without element identity, e.g. `events`
+    Modified(&'static str),
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Change::Added(kind, name) => write!(f, "+ {kind} {name}"),
+            Change::Removed(kind, name) => write!(f, "- {kind} {name}"),
+            Change::Renamed(kind, from, to) => write!(f, "~ {kind} {from} -> {to}"),
+            Change::Changed(kind, name) => write!(f, "~ {kind} {name}"),
+            Change::ComponentFieldAdded { component, field } => {
+                write!(f, "+ component {component}.{field}")
+            }
+            Change::ComponentFieldRemoved { component, field } => {
+                write!(f, "- component {component}.{field}")
+            }
+            Change::ComponentFieldChanged { component, field } => {
+                write!(f, "~ component {component}.{field}")
+            }
+            Change::SystemContentChanged(kind, name) => write!(f, "~ {kind} {name}: content"),
+            Change::FeatureEnabled(feature) => write!(f, "+ feature {}", feature.to_feature()),
+            Change::FeatureDisabled(feature) => write!(f, "- feature {}", feature.to_feature()),
+            Change::BevyVersionChanged(from, to) => {
+                write!(f, "~ bevy {} -> {}", from.to_version(), to.to_version())
+            }
+            Change::Modified(field) => write!(f, "~ {field}"),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct ModelDiff {
+    pub changes: Vec<Change>,
+}
+
+impl ModelDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    // Only new bodies of runtime systems can be hot-reloaded
+    pub fn requires_reload(&self) -> bool {
+        self.changes
+            .iter()
+            .any(|c| !matches!(c, Change::SystemContentChanged(ElementKind::System, _)))
+    }
+}
+
+impl Display for ModelDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+impl BevyModel {
+    // Changes turning `self` into `other`
+    pub fn diff(&self, other: &BevyModel) -> ModelDiff {
+        let mut changes = vec![];
+
+        let settings = (&self.bevy_settings, &other.bevy_settings);
+        if settings.0.bevy_version != settings.1.bevy_version {
+            changes.push(Change::BevyVersionChanged(
+                settings.0.bevy_version,
+                settings.1.bevy_version,
+            ));
+        }
+        for feature in &settings.1.features {
+            if !settings.0.features.contains(feature) {
+                changes.push(Change::FeatureEnabled(feature.clone()));
+            }
+        }
+        for feature in &settings.0.features {
+            if !settings.1.features.contains(feature) {
+                changes.push(Change::FeatureDisabled(feature.clone()));
+            }
+        }
+        let mut rest = settings.0.clone();
+        rest.bevy_version = settings.1.bevy_version;
+        rest.features = settings.1.features.clone();
+        if rest != *settings.1 {
+            changes.push(Change::Modified("bevy_settings"));
+        }
+
+        diff_elements(
+            ElementKind::Plugin,
+            &self.plugins,
+            &other.plugins,
+            &mut changes,
+            |_, _, _| (),
+        );
+        diff_elements(
+            ElementKind::Component,
+            &self.components,
+            &other.components,
+            &mut changes,
+            diff_component,
+        );
+        diff_elements(
+            ElementKind::Bundle,
+            &self.bundles,
+            &other.bundles,
+            &mut changes,
+            |_, _, _| (),
+        );
+        diff_elements(
+            ElementKind::Resource,
+            &self.resources,
+            &other.resources,
+            &mut changes,
+            |_, _, _| (),
+        );
+        diff_elements(
+            ElementKind::StartupSystem,
+            &self.startup_systems,
+            &other.startup_systems,
+            &mut changes,
+            |old, new, changes| diff_system(ElementKind::StartupSystem, old, new, changes),
+        );
+        diff_elements(
+            ElementKind::System,
+            &self.systems,
+            &other.systems,
+            &mut changes,
+            |old, new, changes| diff_system(ElementKind::System, old, new, changes),
+        );
+        diff_elements(
+            ElementKind::Custom,
+            &self.custom,
+            &other.custom,
+            &mut changes,
+            |_, _, _| (),
+        );
+
+        macro_rules! modified {
+            ($($field:ident),*) => {
+                $(
+                    if self.$field != other.$field {
+                        changes.push(Change::Modified(stringify!($field)));
+                    }
+                )*
+            };
+        }
+        modified!(
+            meta,
+            events,
+            states,
+            system_sets,
+            entities,
+            assets,
+            custom_assets,
+            imports,
+            examples,
+            plugin_crates
+        );
+
+        ModelDiff { changes }
+    }
+}
+
+// `detail` reports specific changes and aligns `old` with `new` for everything it covered
+fn diff_elements<T: Identified + PartialEq + Clone>(
+    kind: ElementKind,
+    old: &[T],
+    new: &[T],
+    changes: &mut Vec<Change>,
+    detail: impl Fn(&mut T, &T, &mut Vec<Change>),
+) {
+    for n in new {
+        let Some(o) = old.iter().find(|o| o.key() == n.key()) else {
+            changes.push(Change::Added(kind, n.name().to_string()));
+            continue;
+        };
+        if o.name() != n.name() {
+            changes.push(Change::Renamed(
+                kind,
+                o.name().to_string(),
+                n.name().to_string(),
+            ));
+        }
+        let mut o = o.clone();
+        *o.name_mut() = n.name().to_string();
+        detail(&mut o, n, changes);
+        if o != *n {
+            changes.push(Change::Changed(kind, n.name().to_string()));
+        }
+    }
+    for o in old {
+        if !new.iter().any(|n| n.key() == o.key()) {
+            changes.push(Change::Removed(kind, o.name().to_string()));
+        }
+    }
+}
+
+fn diff_component(old: &mut Component, new: &Component, changes: &mut Vec<Change>) {
+    let field = |field: &str| (new.name.clone(), field.to_string());
+    for n in &new.content {
+        match old.content.iter().find(|o| o.name == n.name) {
+            None => {
+                let (component, field) = field(&n.name);
+                changes.push(Change::ComponentFieldAdded { component, field });
+            }
+            Some(o) if o != n => {
+                let (component, field) = field(&n.name);
+                changes.push(Change::ComponentFieldChanged { component, field });
+            }
+            Some(_) => (),
+        }
+    }
+    for o in &old.content {
+        if !new.content.iter().any(|n| n.name == o.name) {
+            let (component, field) = field(&o.name);
+            changes.push(Change::ComponentFieldRemoved { component, field });
+        }
+    }
+    old.content = new.content.clone();
+}
+
+fn diff_system(kind: ElementKind, old: &mut System, new: &System, changes: &mut Vec<Change>) {
+    if old.content != new.content {
+        changes.push(Change::SystemContentChanged(kind, new.name.clone()));
+        old.content = new.content.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::{ComponentField, ModelId, Plugin, Settings};
+
+    use super::*;
+
+    fn model() -> BevyModel {
+        BevyModel {
+            plugins: vec![Plugin {
+                id: ModelId(1),
+                name: "EditorPlugin".to_string(),
+                ..Default::default()
+            }],
+            components: vec![Component {
+                id: ModelId(2),
+                name: "Ship".to_string(),
+                content: vec![ComponentField {
+                    name: "speed".to_string(),
+                    ty: "f32".to_string(),
+                    default: None,
+                }],
+                ..Default::default()
+            }],
+            systems: vec![System {
+                id: ModelId(3),
+                name: "movement".to_string(),
+                content: "println!(\"move\");".to_string(),
+                ..Default::default()
+            }],
+            last_id: 3,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn same_model_has_no_changes() {
+        assert!(model().diff(&model()).is_empty());
+    }
+
+    #[test]
+    fn diff_reports_typed_changes() {
+        let mut other = model();
+        other.plugins.clear();
+        other.components[0].name = "Player".to_string();
+        other.components[0].content[0].ty = "f64".to_string();
+        other.components[0].content.push(ComponentField {
+            name: "lives".to_string(),
+            ty: "u8".to_string(),
+            default: None,
+        });
+        other.systems[0].content = "println!(\"moved\");".to_string();
+        other.bevy_settings = Settings {
+            features: vec![Feature::Png],
+            ..Default::default()
+        };
+
+        let diff = model().diff(&other);
+        assert_eq!(
+            "+ feature png
+- plugin EditorPlugin
+~ component Ship -> Player
+~ component Player.speed
++ component Player.lives
+~ system movement: content
+",
+            diff.to_string()
+        );
+        assert!(diff.requires_reload());
+    }
+
+    #[test]
+    fn runtime_system_content_is_hot_reloadable() {
+        let mut other = model();
+        other.systems[0].content = "println!(\"moved\");".to_string();
+        let diff = model().diff(&other);
+        assert_eq!(
+            vec![Change::SystemContentChanged(
+                ElementKind::System,
+                "movement".to_string()
+            )],
+            diff.changes
+        );
+        assert!(!diff.requires_reload());
+    }
+}
@@ -4,15 +4,20 @@ use std::{
     path::Path,
 };
 
-use codegen::{Field, Function, Scope, Struct};
+use codegen::{Enum, Field, Function, Impl, Scope, Struct};
 use rust_format::{Formatter, RustFmt};
 
 use crate::{
-    model::{BevyModel, BevyType, Component, Custom, Import, Plugin, System, Used},
+    model::{
+        AssetFormat, BevyModel, BevyType, Bundle, Component, ComponentShape, Custom, CustomAsset,
+        Event, Import, Plugin, PluginMember, Resource, ResourceInit, State, System, SystemSet,
+        Used,
+    },
     templates::{
-        default_cargo_components_template, default_cargo_src_template,
-        default_cargo_systems_template,
+        default_cargo_components_template, default_cargo_plugin_crate_template,
+        default_cargo_src_template, default_cargo_systems_template,
     },
+    validate::Severity,
 };
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
@@ -27,47 +32,138 @@ impl BevyModel {
     pub fn generate_code(&self, mut scope: Scope, gen_type: GenerationType) -> Scope {
         match gen_type {
             GenerationType::Main => {
+                let backend = self.bevy_settings.bevy_version.backend();
+
                 let mut plugin_app_code: String = "".into();
-                for plugin in &self.plugins {
-                    if plugin.is_group {
-                        plugin_app_code
-                            .push_str(format!(".add_plugins({})", &plugin.name).as_str());
-                    } else {
-                        plugin_app_code.push_str(format!(".add_plugin({})", &plugin.name).as_str());
+                let crate_plugins = self.plugin_crates.iter().filter_map(|p| p.crate_plugin());
+                for plugin in self.plugins.iter().cloned().chain(crate_plugins) {
+                    plugin_app_code.push_str(&backend.add_plugin(&plugin));
+                }
+
+                let mut resource_app_code: String = "".into();
+                for resource in self.resources.iter().chain(self.assets_resource().as_ref()) {
+                    match &resource.init {
+                        ResourceInit::Init => resource_app_code
+                            .push_str(format!(".init_resource::<{}>()", &resource.name).as_str()),
+                        ResourceInit::Insert(value) => resource_app_code
+                            .push_str(format!(".insert_resource({value})").as_str()),
                     }
                 }
 
-                let mut startup_system_app_code: String = "".into();
-                for system in &self.startup_systems {
-                    startup_system_app_code.push_str(
-                        format!(".add_startup_system(systems::{})", &system.name).as_str(),
+                if let Some(timestep) = &self.bevy_settings.fixed_timestep {
+                    resource_app_code.push_str(
+                        format!(
+                            ".insert_resource(FixedTime::new_from_secs({:?}))",
+                            timestep.as_secs_f32()
+                        )
+                        .as_str(),
+                    );
+                }
+
+                let mut asset_app_code: String = "".into();
+                for asset in &self.custom_assets {
+                    asset_app_code.push_str(
+                        format!(
+                            ".add_asset::<{}>().init_asset_loader::<{}>()",
+                            &asset.name,
+                            asset.loader_name()
+                        )
+                        .as_str(),
                     );
                 }
 
+                let mut event_app_code: String = "".into();
+                for event in &self.events {
+                    event_app_code.push_str(format!(".add_event::<{}>()", &event.name).as_str());
+                }
+
+                let mut state_app_code: String = "".into();
+                for state in &self.states {
+                    state_app_code.push_str(format!(".add_state::<{}>()", &state.name).as_str());
+                }
+
+                let startup_systems = self.all_startup_systems();
+                let mut system_set_app_code: String = "".into();
+                for set in &self.system_sets {
+                    if let Some(config) = system_set_app_config(set) {
+                        system_set_app_code.push_str(&backend.configure_set(
+                            &config,
+                            &set_members(set, &startup_systems),
+                            &set_members(set, &self.systems),
+                        ));
+                    }
+                }
+
+                let mut register_type_app_code: String = "".into();
+                let reflected_components = self.components.iter().filter(|c| c.is_reflected);
+                // External resources are registered by the crate that defines them
+                let reflected_resources = self
+                    .resources
+                    .iter()
+                    .filter(|r| r.is_reflected && !r.is_external);
+                for name in reflected_components
+                    .map(|c| &c.name)
+                    .chain(reflected_resources.map(|r| &r.name))
+                {
+                    register_type_app_code.push_str(format!(".register_type::<{name}>()").as_str());
+                }
+
+                let mut startup_system_app_code: String = "".into();
+                for system in &startup_systems {
+                    let modifiers =
+                        system_app_modifiers(system, &startup_systems, &self.system_sets);
+                    startup_system_app_code
+                        .push_str(&backend.add_startup_system(system, &modifiers));
+                }
+
                 let mut system_app_code: String = "".into();
                 for system in &self.systems {
-                    system_app_code.push_str(format!(".add_system({})", &system.name).as_str());
+                    let modifiers = system_app_modifiers(system, &self.systems, &self.system_sets);
+                    system_app_code.push_str(&backend.add_system(system, &modifiers));
                 }
 
+                // A plugin group lists its plugins as members instead
                 let mut app_code_merge: String = "".to_owned();
-                app_code_merge.push_str(&plugin_app_code);
+                if !matches!(self.meta.bevy_type, BevyType::PluginGroup(_)) {
+                    app_code_merge.push_str(&plugin_app_code);
+                }
+                app_code_merge.push_str(&register_type_app_code);
+                app_code_merge.push_str(&resource_app_code);
+                app_code_merge.push_str(&asset_app_code);
+                app_code_merge.push_str(&event_app_code);
+                app_code_merge.push_str(&state_app_code);
+                app_code_merge.push_str(&system_set_app_code);
                 app_code_merge.push_str(&startup_system_app_code);
                 app_code_merge.push_str(&system_app_code);
 
                 match &self.meta.bevy_type {
                     BevyType::Plugin(name) => scope.create_plugin(
                         Plugin {
+                            id: Default::default(),
                             name: name.to_string(),
                             is_group: false,
                             dependencies: vec![],
+                            members: vec![],
                         },
                         &app_code_merge,
                     ),
                     BevyType::PluginGroup(name) => scope.create_plugin(
                         Plugin {
+                            id: Default::default(),
                             name: name.to_string(),
                             is_group: true,
                             dependencies: vec![],
+                            members: self
+                                .plugins
+                                .iter()
+                                .cloned()
+                                .chain(self.plugin_crates.iter().filter_map(|p| p.crate_plugin()))
+                                .filter(|p| !p.is_group)
+                                .map(|p| PluginMember {
+                                    name: p.name,
+                                    ..Default::default()
+                                })
+                                .collect(),
                         },
                         &app_code_merge,
                     ),
@@ -75,19 +171,60 @@ impl BevyModel {
                 };
             }
             GenerationType::Components => {
+                let backend = self.bevy_settings.bevy_version.backend();
+
                 for component in &self.components {
-                    scope.create_component(Component {
-                        name: component.name.clone(),
-                        content: component.content.clone(),
-                        attributes: component.attributes.clone(),
-                        derives: component.derives.clone(),
-                        is_reflected: component.is_reflected,
-                    });
+                    match component.shape {
+                        ComponentShape::Enum(_) => {
+                            scope.create_component_enum(component.clone());
+                        }
+                        _ => {
+                            scope.create_component(component.clone());
+                        }
+                    }
+                }
+
+                for bundle in &self.bundles {
+                    scope.create_bundle(bundle.clone());
+                }
+
+                for resource in self.resources.iter().filter(|r| !r.is_external) {
+                    scope.create_resource(resource.clone());
+                }
+
+                if let (Some(resource), Some(content)) =
+                    (self.assets_resource(), self.load_assets_content())
+                {
+                    scope.create_resource(resource.clone());
+                    scope.create_from_world(&resource.name, &content);
+                }
+
+                for asset in &self.custom_assets {
+                    let a = scope.create_custom_asset(asset.clone());
+                    for derive in backend.custom_asset_derives() {
+                        a.derive(derive);
+                    }
+                    scope.create_asset_loader(asset.clone());
+                }
+
+                for event in &self.events {
+                    let a = scope.create_event(event.clone());
+                    for derive in backend.event_derives() {
+                        a.derive(derive);
+                    }
+                }
+
+                for state in &self.states {
+                    scope.create_state(state.clone());
+                }
+
+                for set in &self.system_sets {
+                    scope.create_system_set(set.clone());
                 }
             }
             GenerationType::Systems => {
-                for system in &self.startup_systems {
-                    scope.create_query(system.clone());
+                for system in self.all_startup_systems() {
+                    scope.create_query(system);
                 }
 
                 for system in &self.systems {
@@ -102,20 +239,68 @@ impl BevyModel {
             }
         }
 
-        /*if self.meta.bevy_type.eq(&BevyType::Example) {
-            scope.import("bevy_test", "BevyTest");
-        }*/
-
         scope
     }
 
+    // Standalone `examples/<name>.rs` using the plugin crate, own components and systems
+    // become inline modules
+    pub fn generate_example(&self, crate_name: &str) -> std::io::Result<String> {
+        let mut code = format!("use bevy::prelude::*;\nuse {crate_name}::*;\n");
+        for imp in self.imports.iter().filter(|i| i.used == Used::Main) {
+            code.push_str(&import_format(imp.clone()));
+        }
+
+        let mut modules = "".to_owned();
+        for (module, gen_type) in [
+            ("components", GenerationType::Components),
+            ("systems", GenerationType::Systems),
+        ] {
+            let inner = self.generate_code(Scope::new(), gen_type).to_string();
+            if !inner.is_empty() {
+                code.push_str(&format!("use {module}::*;\n"));
+                modules.push_str(&format!("\nmod {module} {{\nuse super::*;\n\n{inner}}}\n"));
+            }
+        }
+
+        code.push('\n');
+        code.push_str(
+            &self
+                .generate_code(Scope::new(), GenerationType::Main)
+                .to_string(),
+        );
+        code.push_str(&modules);
+        RustFmt::default()
+            .format_str(code)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+    }
+
+    // Startup systems including the one spawning `entities`
+    pub(crate) fn all_startup_systems(&self) -> Vec<System> {
+        let mut startup_systems = self.startup_systems.clone();
+        startup_systems.extend(self.spawn_system());
+        startup_systems
+    }
+
     pub fn generate(&self, gen_type: GenerationType) -> std::io::Result<()> {
+        let errors = self
+            .validate()
+            .into_iter()
+            .filter(|d| d.severity == Severity::Error)
+            .map(|d| d.to_string())
+            .collect::<Vec<String>>();
+        if !errors.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                errors.join("\n"),
+            ));
+        }
+
         let res = generate_structure(self.clone(), gen_type);
         if let Ok(mut bevy_lib_file) = res {
             //println!("Structure done");
             let r = RustFmt::default()
                 .format_str(self.generate_code(Scope::new(), gen_type).to_string())
-                .unwrap();
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
             //println!("Test: {:?}", r);
             bevy_lib_file.write_all(r.as_bytes())?;
         } else {
@@ -126,6 +311,80 @@ impl BevyModel {
     }
 }
 
+fn system_set_app_config(set: &SystemSet) -> Option<String> {
+    let mut config = set.name.clone();
+    for label in &set.before {
+        config.push_str(&format!(".before({label})"));
+    }
+    for label in &set.after {
+        config.push_str(&format!(".after({label})"));
+    }
+    for condition in &set.run_if {
+        config.push_str(&format!(".run_if({condition})"));
+    }
+    if config.eq(&set.name) {
+        None
+    } else {
+        Some(config)
+    }
+}
+
+fn set_members<'a>(set: &SystemSet, systems: &'a [System]) -> Vec<&'a System> {
+    systems
+        .iter()
+        .filter(|s| s.sets.contains(&set.name))
+        .collect()
+}
+
+// State and schedule placement is left to the version specific backend
+fn system_app_modifiers(system: &System, siblings: &[System], sets: &[SystemSet]) -> String {
+    let mut config = "".to_owned();
+    let mut after = system.after.clone();
+    for set_name in &system.sets {
+        config.push_str(&format!(".in_set({set_name})"));
+        let chained = sets.iter().any(|s| s.name.eq(set_name) && s.chain);
+        if !chained {
+            continue;
+        }
+        //Chained sets order their members by declaration
+        let previous = siblings
+            .iter()
+            .take_while(|s| s.name != system.name)
+            .filter(|s| s.sets.contains(set_name))
+            .last();
+        if let Some(previous) = previous {
+            if !after.contains(&previous.name) {
+                after.push(previous.name.clone());
+            }
+        }
+    }
+    for label in &system.before {
+        config.push_str(&format!(".before({label})"));
+    }
+    for label in &after {
+        config.push_str(&format!(".after({label})"));
+    }
+    for condition in &system.run_if {
+        config.push_str(&format!(".run_if({condition})"));
+    }
+    config
+}
+
+pub(crate) fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i != 0 {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
 fn import_format(import: Import) -> String {
     let name = import.dependency.name;
     let ps = import.dependency.paths;
@@ -141,7 +400,22 @@ fn generate_structure(bm: BevyModel, gen_type: GenerationType) -> std::io::Resul
         _ => "",
     };
     let bevy_folder = bm.meta.name.clone();
+    let scene = match bm.bevy_settings.export_scene {
+        true => bm.scene_ron(),
+        false => None,
+    };
     let _ = fs::create_dir(&bevy_folder);
+    if gen_type.eq(&GenerationType::Main) {
+        for plugin_crate in bm.all_plugin_crates() {
+            generate_plugin_crate(&bevy_folder, plugin_crate)?;
+        }
+        if matches!(
+            bm.meta.bevy_type,
+            BevyType::Plugin(_) | BevyType::PluginGroup(_)
+        ) {
+            generate_examples(&bevy_folder, &bm)?;
+        }
+    }
     if !folder.is_empty() {
         fs::create_dir_all(bevy_folder.to_owned() + "/" + folder)?
     };
@@ -156,8 +430,8 @@ fn generate_structure(bm: BevyModel, gen_type: GenerationType) -> std::io::Resul
     let buf = match gen_type {
         GenerationType::All => todo!(),
         GenerationType::Main => default_cargo_src_template(&bm),
-        GenerationType::Components => default_cargo_components_template(),
-        GenerationType::Systems => default_cargo_systems_template(),
+        GenerationType::Components => default_cargo_components_template(&bm),
+        GenerationType::Systems => default_cargo_systems_template(&bm),
     };
     cargo_file.write_all(buf.as_bytes())?;
 
@@ -194,6 +468,10 @@ fn generate_structure(bm: BevyModel, gen_type: GenerationType) -> std::io::Resul
     //Add bevy prelude
     let _ = bevy_lib_file.write(("use bevy::prelude::*;\n").as_bytes());
 
+    if gen_type.eq(&GenerationType::Main) {
+        let _ = bevy_lib_file.write(("use components::*;\n").as_bytes());
+    }
+
     for imp in bm.imports {
         match imp.used {
             Used::Main if gen_type.eq(&GenerationType::Main) => {
@@ -262,6 +540,14 @@ mod systems_hot {
             .as_bytes(),
         );
 
+        //Scene
+        if let Some(scene) = scene {
+            let path = bevy_folder.to_owned() + "/assets";
+            fs::create_dir_all(&path)?;
+            let mut scene_file = File::create(format!("{path}/{}.scn.ron", bm.meta.name))?;
+            let _ = scene_file.write(scene.as_bytes());
+        }
+
         //Assets
         let _ = copy_dir_all(bm.meta.asset_path, bevy_folder + "/assets");
     }
@@ -274,6 +560,60 @@ mod systems_hot {
     Ok(bevy_lib_file)
 }
 
+// Single crate with the plugin in lib.rs and `components`/`systems` as modules
+fn generate_plugin_crate(root_folder: &str, bm: &BevyModel) -> std::io::Result<()> {
+    let path = format!("{root_folder}/{}/src", bm.meta.name);
+    fs::create_dir_all(&path)?;
+    let mut cargo_file = File::create(format!("{root_folder}/{}/Cargo.toml", bm.meta.name))?;
+    cargo_file.write_all(default_cargo_plugin_crate_template(bm).as_bytes())?;
+
+    for (filename, gen_type, header, used) in [
+        (
+            "lib.rs",
+            GenerationType::Main,
+            "pub mod components;\npub mod systems;\n\nuse bevy::prelude::*;\nuse components::*;\nuse systems::*;\n",
+            Used::Main,
+        ),
+        (
+            "components.rs",
+            GenerationType::Components,
+            "use bevy::prelude::*;\n",
+            Used::Components,
+        ),
+        (
+            "systems.rs",
+            GenerationType::Systems,
+            "use bevy::prelude::*;\n\nuse crate::components::*;\n",
+            Used::Systems,
+        ),
+    ] {
+        let mut file = File::create(format!("{path}/{filename}"))?;
+        file.write_all(header.as_bytes())?;
+        for imp in bm.imports.iter().filter(|i| i.used == used) {
+            file.write_all(import_format(imp.clone()).as_bytes())?;
+        }
+        file.write_all("\n".as_bytes())?;
+        let code = RustFmt::default()
+            .format_str(bm.generate_code(Scope::new(), gen_type).to_string())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+        file.write_all(code.as_bytes())?;
+    }
+    generate_examples(&format!("{root_folder}/{}", bm.meta.name), bm)
+}
+
+fn generate_examples(folder: &str, bm: &BevyModel) -> std::io::Result<()> {
+    if bm.examples.is_empty() {
+        return Ok(());
+    }
+    let path = format!("{folder}/examples");
+    fs::create_dir_all(&path)?;
+    for example in &bm.examples {
+        let mut file = File::create(format!("{path}/{}.rs", example.crate_name()))?;
+        file.write_all(example.generate_example(&bm.crate_name())?.as_bytes())?;
+    }
+    Ok(())
+}
+
 // https://stackoverflow.com/questions/26958489/how-to-copy-a-folder-recursively-in-rust
 fn copy_dir_all(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> io::Result<()> {
     fs::create_dir_all(&dst)?;
@@ -294,10 +634,30 @@ trait BevyCodegen {
 
     fn create_plugin(&mut self, plugin: Plugin, content: &str) -> &mut Function;
 
+    fn create_plugin_group(&mut self, plugin: Plugin, content: &str) -> &mut Function;
+
     fn create_query(&mut self, system: System) -> &mut Function;
 
     fn create_component(&mut self, component: Component) -> &mut Struct;
 
+    fn create_component_enum(&mut self, component: Component) -> &mut Enum;
+
+    fn create_bundle(&mut self, bundle: Bundle) -> &mut Struct;
+
+    fn create_resource(&mut self, resource: Resource) -> &mut Struct;
+
+    fn create_from_world(&mut self, name: &str, content: &str) -> &mut Function;
+
+    fn create_event(&mut self, event: Event) -> &mut Struct;
+
+    fn create_state(&mut self, state: State) -> &mut Impl;
+
+    fn create_system_set(&mut self, set: SystemSet) -> &mut Struct;
+
+    fn create_custom_asset(&mut self, asset: CustomAsset) -> &mut Struct;
+
+    fn create_asset_loader(&mut self, asset: CustomAsset) -> &mut Scope;
+
     fn generate(&mut self) -> String;
 }
 
@@ -308,12 +668,12 @@ impl BevyCodegen for Scope {
     }
 
     fn create_plugin(&mut self, plugin: Plugin, content: &str) -> &mut Function {
+        if plugin.is_group {
+            return self.create_plugin_group(plugin, content);
+        }
         self.new_struct(&plugin.name).vis("pub");
-        let plugin_impl = match &plugin.is_group {
-            false => self.new_impl(&plugin.name).impl_trait("Plugin"),
-            true => self.new_impl(&plugin.name).impl_trait("Plugins"),
-        };
-        plugin_impl
+        self.new_impl(&plugin.name)
+            .impl_trait("Plugin")
             .new_fn("build")
             .arg_ref_self()
             .arg("app", "&mut App")
@@ -322,10 +682,59 @@ impl BevyCodegen for Scope {
             .line(";")
     }
 
+    // App content can't be added to a group directly, it goes into a member plugin
+    fn create_plugin_group(&mut self, plugin: Plugin, content: &str) -> &mut Function {
+        let mut members = plugin.members;
+        if !content.trim().is_empty() {
+            let name = format!("{}Plugin", plugin.name.trim_end_matches("Plugins"));
+            self.create_plugin(
+                Plugin {
+                    id: Default::default(),
+                    name: name.clone(),
+                    is_group: false,
+                    dependencies: vec![],
+                    members: vec![],
+                },
+                content,
+            );
+            members.insert(
+                0,
+                PluginMember {
+                    name,
+                    ..Default::default()
+                },
+            );
+        }
+
+        self.new_struct(&plugin.name).vis("pub");
+        let build = self
+            .new_impl(&plugin.name)
+            .impl_trait("PluginGroup")
+            .new_fn("build")
+            .arg_self()
+            .ret("bevy::app::PluginGroupBuilder")
+            .line("bevy::app::PluginGroupBuilder::start::<Self>()");
+        for member in &members {
+            build.line(format!(".add({})", member.name));
+            if let Some(config) = &member.config {
+                build.line(format!(".set({config})"));
+            }
+            if !member.enabled {
+                build.line(format!(".disable::<{}>()", member.name));
+            }
+        }
+        build
+    }
+
     fn create_query(&mut self, system: System) -> &mut Function {
         let mut fun = self.new_fn(system.name.as_str());
-        for (name, ty) in &system.param {
-            fun = fun.arg(name, ty);
+        for param in &system.param {
+            let name = if param.is_mut() {
+                format!("mut {}", param.name())
+            } else {
+                param.name().to_string()
+            };
+            fun = fun.arg(&name, param.ty());
         }
         if !system.visibility.is_empty() {
             fun.vis(&system.visibility);
@@ -337,12 +746,51 @@ impl BevyCodegen for Scope {
     }
 
     fn create_component(&mut self, component: Component) -> &mut Struct {
+        let has_defaults = component.content.iter().any(|f| f.default.is_some());
+        if has_defaults {
+            let default_fn = self
+                .new_impl(&component.name)
+                .impl_trait("Default")
+                .new_fn("default")
+                .ret("Self");
+            let default_of = |name: &str| {
+                component
+                    .content
+                    .iter()
+                    .find(|f| f.name == name)
+                    .and_then(|f| f.default.as_deref())
+                    .unwrap_or("Default::default()")
+            };
+            match &component.shape {
+                ComponentShape::Tuple(types) => {
+                    let values = (0..types.len()).map(|i| default_of(&i.to_string()));
+                    default_fn.line(format!("Self({})", values.collect::<Vec<_>>().join(", ")));
+                }
+                _ => {
+                    default_fn.line("Self {");
+                    for field in component.content.iter() {
+                        default_fn.line(format!("{}: {},", field.name, default_of(&field.name)));
+                    }
+                    default_fn.line("}");
+                }
+            }
+        }
         let a = self.new_struct(&component.name);
         a.vis("pub");
-        for (n, t) in component.content.iter() {
-            let mut f = Field::new(n, t);
-            f.vis("pub");
-            a.push_field(f);
+        match &component.shape {
+            ComponentShape::Struct => {
+                for field in component.content.iter() {
+                    let mut f = Field::new(&field.name, &field.ty);
+                    f.vis("pub");
+                    a.push_field(f);
+                }
+            }
+            ComponentShape::Tuple(types) => {
+                for t in types.iter() {
+                    a.tuple_field(format!("pub {t}"));
+                }
+            }
+            ComponentShape::Unit | ComponentShape::Enum(_) => (),
         }
         for attribute in component.attributes.iter() {
             a.attr(attribute);
@@ -353,12 +801,209 @@ impl BevyCodegen for Scope {
         }
         if component.is_reflected {
             a.derive("Reflect");
-            a.derive("Default");
+            if !has_defaults {
+                a.derive("Default");
+            }
             a.attr("reflect(Component)");
         }
         a
     }
 
+    fn create_component_enum(&mut self, component: Component) -> &mut Enum {
+        let variants = match &component.shape {
+            ComponentShape::Enum(variants) => variants.clone(),
+            _ => vec![],
+        };
+        if component.is_reflected {
+            //Reflected components need a default, use the first variant
+            if let Some(first) = variants.first() {
+                let fields = vec!["Default::default()"; first.fields.len()];
+                let value = if fields.is_empty() {
+                    format!("Self::{}", first.name)
+                } else {
+                    format!("Self::{}({})", first.name, fields.join(", "))
+                };
+                self.new_impl(&component.name)
+                    .impl_trait("Default")
+                    .new_fn("default")
+                    .ret("Self")
+                    .line(value);
+            }
+        }
+        let a = self.new_enum(&component.name);
+        a.vis("pub");
+        for variant in variants.iter() {
+            let v = a.new_variant(&variant.name);
+            for t in variant.fields.iter() {
+                v.tuple(t);
+            }
+        }
+        // `Enum` has no `attr`, attributes go in as macros
+        for attribute in component.attributes.iter() {
+            a.r#macro(&format!("#[{attribute}]"));
+        }
+        a.derive("Component");
+        for derive in component.derives.iter() {
+            a.derive(derive);
+        }
+        if component.is_reflected {
+            a.derive("Reflect");
+            a.r#macro("#[reflect(Component)]");
+        }
+        a
+    }
+
+    fn create_bundle(&mut self, bundle: Bundle) -> &mut Struct {
+        let a = self.new_struct(&bundle.name);
+        a.vis("pub");
+        for component in bundle.components.iter() {
+            let mut f = Field::new(&snake_case(component), component);
+            f.vis("pub");
+            a.push_field(f);
+        }
+        for attribute in bundle.attributes.iter() {
+            a.attr(attribute);
+        }
+        a.derive("Bundle");
+        for derive in bundle.derives.iter() {
+            a.derive(derive);
+        }
+        a
+    }
+
+    fn create_resource(&mut self, resource: Resource) -> &mut Struct {
+        let a = self.new_struct(&resource.name);
+        a.vis("pub");
+        for (n, t) in resource.content.iter() {
+            let mut f = Field::new(n, t);
+            f.vis("pub");
+            a.push_field(f);
+        }
+        for attribute in resource.attributes.iter() {
+            a.attr(attribute);
+        }
+        a.derive("Resource");
+        for derive in resource.derives.iter() {
+            a.derive(derive);
+        }
+        if resource.is_reflected {
+            a.derive("Reflect");
+            a.derive("Default");
+            a.attr("reflect(Resource)");
+        }
+        a
+    }
+
+    fn create_from_world(&mut self, name: &str, content: &str) -> &mut Function {
+        self.new_impl(name)
+            .impl_trait("FromWorld")
+            .new_fn("from_world")
+            .arg("world", "&mut World")
+            .ret("Self")
+            .line(content)
+    }
+
+    fn create_event(&mut self, event: Event) -> &mut Struct {
+        let a = self.new_struct(&event.name);
+        a.vis("pub");
+        for (n, t) in event.content.iter() {
+            let mut f = Field::new(n, t);
+            f.vis("pub");
+            a.push_field(f);
+        }
+        for attribute in event.attributes.iter() {
+            a.attr(attribute);
+        }
+        for derive in event.derives.iter() {
+            a.derive(derive);
+        }
+        a
+    }
+
+    fn create_state(&mut self, state: State) -> &mut Impl {
+        let a = self.new_enum(&state.name);
+        a.vis("pub");
+        for derive in [
+            "States",
+            "Debug",
+            "Clone",
+            "Copy",
+            "Eq",
+            "PartialEq",
+            "Hash",
+        ] {
+            a.derive(derive);
+        }
+        for variant in state.variants.iter() {
+            a.new_variant(variant);
+        }
+        let b = self.new_impl(&state.name);
+        b.impl_trait("Default")
+            .new_fn("default")
+            .ret("Self")
+            .line(format!("Self::{}", state.initial));
+        b
+    }
+
+    fn create_system_set(&mut self, set: SystemSet) -> &mut Struct {
+        let a = self.new_struct(&set.name);
+        a.vis("pub");
+        for derive in ["SystemSet", "Debug", "Hash", "PartialEq", "Eq", "Clone"] {
+            a.derive(derive);
+        }
+        a
+    }
+
+    fn create_custom_asset(&mut self, asset: CustomAsset) -> &mut Struct {
+        let a = self.new_struct(&asset.name);
+        a.vis("pub");
+        for (n, t) in asset.content.iter() {
+            let mut f = Field::new(n, t);
+            f.vis("pub");
+            a.push_field(f);
+        }
+        for derive in ["serde::Deserialize", "bevy::reflect::TypeUuid", "Debug"] {
+            a.derive(derive);
+        }
+        a.attr(format!("uuid = \"{}\"", asset.uuid));
+        a
+    }
+
+    // The trait method needs explicit lifetimes on `self`, which codegen can't express
+    fn create_asset_loader(&mut self, asset: CustomAsset) -> &mut Scope {
+        let loader = asset.loader_name();
+        self.new_struct(&loader).vis("pub").derive("Default");
+        let deserialize = match asset.format {
+            AssetFormat::Ron => format!("ron::de::from_bytes::<{}>(bytes)?", asset.name),
+            AssetFormat::Json => format!("serde_json::from_slice::<{}>(bytes)?", asset.name),
+        };
+        let extensions = asset
+            .extensions
+            .iter()
+            .map(|e| format!("\"{e}\""))
+            .collect::<Vec<String>>()
+            .join(", ");
+        self.raw(format!(
+            r#"impl bevy::asset::AssetLoader for {loader} {{
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut bevy::asset::LoadContext,
+    ) -> bevy::utils::BoxedFuture<'a, Result<(), bevy::asset::Error>> {{
+        Box::pin(async move {{
+            let asset = {deserialize};
+            load_context.set_default_asset(bevy::asset::LoadedAsset::new(asset));
+            Ok(())
+        }})
+    }}
+
+    fn extensions(&self) -> &[&str] {{
+        &[{extensions}]
+    }}
+}}"#
+        ))
+    }
+
     fn generate(&mut self) -> String {
         RustFmt::default().format_str(self.to_string()).unwrap()
     }
@@ -366,7 +1011,13 @@ impl BevyCodegen for Scope {
 
 #[cfg(test)]
 mod tests {
-    use crate::model::System;
+    use std::time::Duration;
+
+    use crate::model::{
+        Asset, AssetFormat, BevyVersion, ComponentField, ComponentVariant, CustomAsset,
+        EntityPrefab, Meta, QueryData, QueryFilter, Settings, StatePlacement, System, SystemParam,
+        SystemSchedule,
+    };
 
     use super::*;
 
@@ -388,7 +1039,7 @@ r#"fn main() {
     #[rustfmt::skip]
     fn create_plugin_works() {
         let mut scp = Scope::new();
-        scp.create_plugin(Plugin { name: "TestPlugin".to_string(), is_group: false, dependencies: vec![] }, "");
+        scp.create_plugin(Plugin { id: Default::default(), name: "TestPlugin".to_string(), is_group: false, dependencies: vec![], members: vec![] }, "");
         assert_eq!(
             scp.generate(),
 r#"pub struct TestPlugin;
@@ -406,20 +1057,102 @@ impl Plugin for TestPlugin {
     #[rustfmt::skip]
     fn create_plugin_group_works() {
         let mut scp = Scope::new();
-        scp.create_plugin(Plugin{ name: "TestPlugins".to_string(), is_group: true, dependencies: vec![] }, "");
+        scp.create_plugin(Plugin{ id: Default::default(), name: "TestPlugins".to_string(), is_group: true, dependencies: vec![], members: vec![] }, "");
         assert_eq!(
             scp.generate(),
 r#"pub struct TestPlugins;
 
-impl Plugins for TestPlugins {
+impl PluginGroup for TestPlugins {
+    fn build(self) -> bevy::app::PluginGroupBuilder {
+        bevy::app::PluginGroupBuilder::start::<Self>()
+    }
+}
+"#
+        );
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn create_plugin_group_with_members_works() {
+        let mut scp = Scope::new();
+        let members = vec![
+            PluginMember { name: "MovementPlugin".to_string(), ..Default::default() },
+            PluginMember { name: "DebugPlugin".to_string(), enabled: false, config: None },
+            PluginMember { name: "CameraPlugin".to_string(), enabled: true, config: Some("CameraPlugin { zoom: 2.0 }".to_string()) },
+        ];
+        scp.create_plugin(Plugin{ id: Default::default(), name: "GamePlugins".to_string(), is_group: true, dependencies: vec![], members }, "");
+        assert_eq!(
+            scp.generate(),
+r#"pub struct GamePlugins;
+
+impl PluginGroup for GamePlugins {
+    fn build(self) -> bevy::app::PluginGroupBuilder {
+        bevy::app::PluginGroupBuilder::start::<Self>()
+            .add(MovementPlugin)
+            .add(DebugPlugin)
+            .disable::<DebugPlugin>()
+            .add(CameraPlugin)
+            .set(CameraPlugin { zoom: 2.0 })
+    }
+}
+"#
+        );
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn create_plugin_group_with_content_works() {
+        let mut scp = Scope::new();
+        scp.create_plugin(Plugin{ id: Default::default(), name: "GamePlugins".to_string(), is_group: true, dependencies: vec![], members: vec![] }, ".add_system(systems::test)");
+        assert_eq!(
+            scp.generate(),
+r#"pub struct GamePlugin;
+
+impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
-        app;
+        app.add_system(systems::test);
+    }
+}
+
+pub struct GamePlugins;
+
+impl PluginGroup for GamePlugins {
+    fn build(self) -> bevy::app::PluginGroupBuilder {
+        bevy::app::PluginGroupBuilder::start::<Self>().add(GamePlugin)
     }
 }
 "#
         );
     }
 
+    #[test]
+    fn generate_main_configures_plugin_group() {
+        let bm = BevyModel {
+            plugins: vec![Plugin {
+                members: vec![
+                    PluginMember {
+                        name: "WindowPlugin".to_string(),
+                        enabled: true,
+                        config: Some("WindowPlugin { ..default() }".to_string()),
+                    },
+                    PluginMember {
+                        name: "LogPlugin".to_string(),
+                        enabled: false,
+                        config: None,
+                    },
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let main = bm
+            .generate_code(Scope::new(), GenerationType::Main)
+            .to_string();
+        assert!(main.contains(
+            ".add_plugins(DefaultPlugins.set(WindowPlugin { ..default() }).disable::<LogPlugin>())"
+        ));
+    }
+
     #[test]
     fn create_simple_query_works() {
         let mut scp = Scope::new();
@@ -429,6 +1162,7 @@ impl Plugins for TestPlugins {
             content: "".to_string(),
             visibility: "".to_string(),
             attributes: vec![],
+            ..Default::default()
         });
         assert_eq!(scp.generate(), "fn test() {}\n");
     }
@@ -439,10 +1173,15 @@ impl Plugins for TestPlugins {
         let mut scp = Scope::new();
         scp.create_query(System {
             name: "test2".to_string(),
-            param: vec![("field".to_string(), "Type".to_string())],
+            param: vec![SystemParam::Custom {
+                name: "field".to_string(),
+                ty: "Type".to_string(),
+                is_mut: false,
+            }],
             content: "".to_string(),
             visibility: "pub".to_string(),
             attributes: vec!["no_mangle".to_string()],
+            ..Default::default()
         });
         assert_eq!(
             scp.generate(),
@@ -452,6 +1191,38 @@ pub fn test2(field: Type) {}
         );
     }
 
+    #[test]
+    #[rustfmt::skip]
+    fn create_query_with_typed_params_works() {
+        let mut scp = Scope::new();
+        scp.create_query(System {
+            name: "move_players".to_string(),
+            param: vec![
+                SystemParam::Commands { name: "commands".to_string() },
+                SystemParam::Res { name: "time".to_string(), ty: "Time".to_string() },
+                SystemParam::Query {
+                    name: "query".to_string(),
+                    data: vec![QueryData::Mut("Transform".to_string())],
+                    filter: vec![QueryFilter::With("Player".to_string())],
+                },
+            ],
+            content: "".to_string(),
+            visibility: "".to_string(),
+            attributes: vec![],
+            ..Default::default()
+        });
+        assert_eq!(
+            scp.generate(),
+r#"fn move_players(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<&mut Transform, With<Player>>,
+) {
+}
+"#
+        );
+    }
+
     #[test]
     #[rustfmt::skip]
     fn create_component_works() {
@@ -478,4 +1249,673 @@ pub struct TestPlugin;
 "#
         );
     }
+
+    #[test]
+    #[rustfmt::skip]
+    fn create_component_with_default_values_works() {
+        let mut scp = Scope::new();
+        scp.create_component(Component {
+            name: "Player".to_string(),
+            content: vec![
+                ComponentField { name: "rotation_speed".to_string(), ty: "f32".to_string(), default: Some("f32::to_radians(180.0)".to_string()) },
+                ComponentField { name: "shooting_timer".to_string(), ty: "Option<f32>".to_string(), default: None },
+            ],
+            ..Default::default()
+        });
+        assert_eq!(
+            scp.generate(),
+r#"impl Default for Player {
+    fn default() -> Self {
+        Self {
+            rotation_speed: f32::to_radians(180.0),
+            shooting_timer: Default::default(),
+        }
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Player {
+    pub rotation_speed: f32,
+    pub shooting_timer: Option<f32>,
+}
+"#
+        );
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn create_tuple_component_with_default_values_works() {
+        let mut scp = Scope::new();
+        scp.create_component(Component {
+            name: "Velocity".to_string(),
+            content: vec![
+                ComponentField { name: "1".to_string(), ty: "f32".to_string(), default: Some("10.0".to_string()) },
+            ],
+            shape: ComponentShape::Tuple(vec!["f32".to_string(), "f32".to_string()]),
+            ..Default::default()
+        });
+        assert_eq!(
+            scp.generate(),
+r#"impl Default for Velocity {
+    fn default() -> Self {
+        Self(Default::default(), 10.0)
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Velocity(pub f32, pub f32);
+"#
+        );
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn create_tuple_component_works() {
+        let mut scp = Scope::new();
+        scp.create_component(Component { name: "Health".to_string(), shape: ComponentShape::Tuple(vec!["f32".to_string()]), is_reflected: false, ..Default::default() });
+        assert_eq!(
+            scp.generate(),
+r#"#[derive(Component)]
+pub struct Health(pub f32);
+"#
+        );
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn create_enum_component_works() {
+        let mut scp = Scope::new();
+        scp.create_component_enum(Component {
+            name: "Team".to_string(),
+            shape: ComponentShape::Enum(vec![
+                ComponentVariant { name: "Red".to_string(), fields: vec![] },
+                ComponentVariant { name: "Blue".to_string(), fields: vec![] },
+                ComponentVariant { name: "Neutral".to_string(), fields: vec!["u8".to_string()] },
+            ]),
+            ..Default::default()
+        });
+        assert_eq!(
+            scp.generate(),
+r#"impl Default for Team {
+    fn default() -> Self {
+        Self::Red
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub enum Team {
+    Red,
+    Blue,
+    Neutral(u8),
+}
+"#
+        );
+    }
+
+    #[test]
+    fn generate_main_registers_reflected_types() {
+        let bm = BevyModel {
+            components: vec![
+                Component {
+                    name: "Player".to_string(),
+                    ..Default::default()
+                },
+                Component {
+                    name: "Bullet".to_string(),
+                    is_reflected: false,
+                    ..Default::default()
+                },
+            ],
+            resources: vec![
+                Resource {
+                    name: "Score".to_string(),
+                    ..Default::default()
+                },
+                Resource {
+                    name: "ClearColor".to_string(),
+                    is_external: true,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let main = bm
+            .generate_code(Scope::new(), GenerationType::Main)
+            .to_string();
+        assert!(main.contains(".register_type::<Player>().register_type::<Score>()"));
+        assert!(!main.contains(".register_type::<Bullet>()"));
+        assert!(!main.contains(".register_type::<ClearColor>()"));
+    }
+
+    #[test]
+    fn generate_spawns_entities_on_startup() {
+        let bm = BevyModel {
+            entities: vec![EntityPrefab {
+                name: "Player".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let main = bm
+            .generate_code(Scope::new(), GenerationType::Main)
+            .to_string();
+        assert!(main.contains(".add_startup_system(systems::spawn_entities)"));
+
+        let systems = bm
+            .generate_code(Scope::new(), GenerationType::Systems)
+            .to_string();
+        assert!(systems.contains("fn spawn_entities(mut commands: Commands)"));
+    }
+
+    #[test]
+    fn generate_loads_asset_manifest() {
+        let bm = BevyModel {
+            assets: vec![Asset {
+                name: "ship".to_string(),
+                path: "ship_C.png".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let main = bm
+            .generate_code(Scope::new(), GenerationType::Main)
+            .to_string();
+        assert!(main.contains(".init_resource::<GameAssets>()"));
+        assert!(!main.contains("load_assets"));
+
+        let components = bm
+            .generate_code(Scope::new(), GenerationType::Components)
+            .to_string();
+        assert!(components.contains("struct GameAssets"));
+        assert!(components.contains("pub ship: Handle<Image>"));
+        assert!(components.contains("impl FromWorld for GameAssets"));
+        assert!(components.contains("fn from_world(world: &mut World) -> Self"));
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn create_custom_asset_works() {
+        let mut scp = Scope::new();
+        let asset = CustomAsset { name: "Level".to_string(), content: vec![("tiles".to_string(), "Vec<u8>".to_string())], extensions: vec!["level.ron".to_string()], ..Default::default() };
+        scp.create_custom_asset(asset.clone());
+        scp.create_asset_loader(asset);
+        assert_eq!(
+            scp.generate(),
+r#"#[derive(serde::Deserialize, bevy::reflect::TypeUuid, Debug)]
+#[uuid = "39cadc56-aa9c-4543-8640-a018b74b5052"]
+pub struct Level {
+    pub tiles: Vec<u8>,
+}
+
+#[derive(Default)]
+pub struct LevelLoader;
+
+impl bevy::asset::AssetLoader for LevelLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut bevy::asset::LoadContext,
+    ) -> bevy::utils::BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let asset = ron::de::from_bytes::<Level>(bytes)?;
+            load_context.set_default_asset(bevy::asset::LoadedAsset::new(asset));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["level.ron"]
+    }
+}
+"#
+        );
+    }
+
+    #[test]
+    fn generate_main_registers_custom_assets() {
+        let bm = BevyModel {
+            custom_assets: vec![CustomAsset {
+                name: "Dialogue".to_string(),
+                format: AssetFormat::Json,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let main = bm
+            .generate_code(Scope::new(), GenerationType::Main)
+            .to_string();
+        assert!(main.contains(".add_asset::<Dialogue>().init_asset_loader::<DialogueLoader>()"));
+
+        let components = bm
+            .generate_code(Scope::new(), GenerationType::Components)
+            .to_string();
+        assert!(components.contains("serde_json::from_slice::<Dialogue>(bytes)?"));
+    }
+
+    #[test]
+    fn generate_main_adds_plugin_crates() {
+        let bm = BevyModel {
+            plugin_crates: vec![
+                BevyModel {
+                    meta: Meta {
+                        name: "enemy-ai".to_string(),
+                        bevy_type: BevyType::Plugin("EnemyAiPlugin".to_string()),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                BevyModel {
+                    meta: Meta {
+                        name: "ui".to_string(),
+                        bevy_type: BevyType::PluginGroup("UiPlugins".to_string()),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let main = bm
+            .generate_code(Scope::new(), GenerationType::Main)
+            .to_string();
+        assert!(main.contains(".add_plugin(enemy_ai::EnemyAiPlugin)"));
+        assert!(main.contains(".add_plugins(ui::UiPlugins)"));
+
+        let cargo = default_cargo_src_template(&bm);
+        assert!(cargo.contains(r#"members = ["systems", "components", "enemy-ai", "ui"]"#));
+        assert!(cargo.contains(r#"enemy-ai = { path = "enemy-ai" }"#));
+    }
+
+    #[test]
+    fn generate_plugin_crate_imports_systems() {
+        let bm = BevyModel {
+            meta: Meta {
+                name: "enemy-ai".to_string(),
+                bevy_type: BevyType::Plugin("EnemyAiPlugin".to_string()),
+                ..Default::default()
+            },
+            systems: vec![System {
+                name: "chase".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let folder = std::env::temp_dir().join("potoo_generate_plugin_crate_imports_systems");
+        generate_plugin_crate(folder.to_str().unwrap(), &bm).unwrap();
+
+        let lib = fs::read_to_string(folder.join("enemy-ai/src/lib.rs")).unwrap();
+        assert!(lib.contains("use components::*;\nuse systems::*;\n"));
+        assert!(lib.contains(".add_system(chase)"));
+        let _ = fs::remove_dir_all(folder);
+    }
+
+    #[test]
+    fn generate_example_uses_plugin_crate() {
+        let example = BevyModel {
+            meta: Meta {
+                name: "basic".to_string(),
+                bevy_type: BevyType::Example,
+                ..Default::default()
+            },
+            plugins: vec![
+                Plugin::default(),
+                Plugin {
+                    id: Default::default(),
+                    name: "EnemyAiPlugin".to_string(),
+                    is_group: false,
+                    dependencies: vec![],
+                    members: vec![],
+                },
+            ],
+            startup_systems: vec![System {
+                name: "setup".to_string(),
+                ..Default::default()
+            }],
+            systems: vec![System {
+                name: "patrol".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let code = example.generate_example("enemy_ai").unwrap();
+        assert!(code
+            .starts_with("use bevy::prelude::*;\nuse enemy_ai::*;\nuse systems::*;\n\nfn main()"));
+        assert!(code.contains(
+            "App::new()\n        .add_plugins(DefaultPlugins)\n        .add_plugin(EnemyAiPlugin)"
+        ));
+        assert!(code.contains(".add_system(patrol)"));
+        assert!(code
+            .contains("mod systems {\n    use super::*;\n\n    #[no_mangle]\n    pub fn setup()"));
+        assert!(!code.contains("mod components"));
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn create_bundle_works() {
+        let mut scp = Scope::new();
+        scp.create_bundle(Bundle { name: "PlayerBundle".to_string(), components: vec!["Player".to_string(), "SpriteBundle".to_string()], ..Default::default() });
+        assert_eq!(
+            scp.generate(),
+r#"#[derive(Bundle)]
+pub struct PlayerBundle {
+    pub player: Player,
+    pub sprite_bundle: SpriteBundle,
+}
+"#
+        );
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn create_resource_works() {
+        let mut scp = Scope::new();
+        scp.create_resource(Resource { name: "Score".to_string(), content: vec![("value".to_string(), "u32".to_string())], ..Default::default() });
+        assert_eq!(
+            scp.generate(),
+r#"#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+pub struct Score {
+    pub value: u32,
+}
+"#
+        );
+    }
+
+    #[test]
+    fn generate_main_registers_resources() {
+        let bm = BevyModel {
+            resources: vec![
+                Resource {
+                    name: "Score".to_string(),
+                    ..Default::default()
+                },
+                Resource {
+                    name: "Msaa".to_string(),
+                    init: ResourceInit::Insert("Msaa::Sample4".to_string()),
+                    is_external: true,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let main = bm
+            .generate_code(Scope::new(), GenerationType::Main)
+            .to_string();
+        assert!(main.contains(".init_resource::<Score>().insert_resource(Msaa::Sample4)"));
+
+        let components = bm
+            .generate_code(Scope::new(), GenerationType::Components)
+            .to_string();
+        assert!(components.contains("struct Score"));
+        assert!(!components.contains("struct Msaa"));
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn create_event_works() {
+        let mut scp = Scope::new();
+        scp.create_event(Event { name: "CollisionEvent".to_string(), content: vec![("entity".to_string(), "Entity".to_string())], derives: vec!["Debug".to_string()], ..Default::default() });
+        assert_eq!(
+            scp.generate(),
+r#"#[derive(Debug)]
+pub struct CollisionEvent {
+    pub entity: Entity,
+}
+"#
+        );
+    }
+
+    #[test]
+    fn generate_main_adds_events() {
+        let bm = BevyModel {
+            events: vec![Event {
+                name: "CollisionEvent".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let main = bm
+            .generate_code(Scope::new(), GenerationType::Main)
+            .to_string();
+        assert!(main.contains(".add_event::<CollisionEvent>()"));
+
+        let components = bm
+            .generate_code(Scope::new(), GenerationType::Components)
+            .to_string();
+        assert!(!components.contains("derive(Event)"));
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn create_state_works() {
+        let mut scp = Scope::new();
+        scp.create_state(State { name: "AppState".to_string(), variants: vec!["Menu".to_string(), "InGame".to_string()], initial: "Menu".to_string() });
+        assert_eq!(
+            scp.generate(),
+r#"#[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum AppState {
+    Menu,
+    InGame,
+}
+
+impl Default for AppState {
+    fn default() -> Self {
+        Self::Menu
+    }
+}
+"#
+        );
+    }
+
+    #[test]
+    fn generate_main_schedules_systems_in_states() {
+        let bm = BevyModel {
+            states: vec![State::default()],
+            systems: vec![
+                System {
+                    name: "setup_menu".to_string(),
+                    state: Some(StatePlacement::OnEnter("AppState::Menu".to_string())),
+                    ..Default::default()
+                },
+                System {
+                    name: "menu".to_string(),
+                    state: Some(StatePlacement::OnUpdate("AppState::Menu".to_string())),
+                    ..Default::default()
+                },
+                System {
+                    name: "cleanup_menu".to_string(),
+                    state: Some(StatePlacement::OnExit("AppState::Menu".to_string())),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let main = bm
+            .generate_code(Scope::new(), GenerationType::Main)
+            .to_string();
+        assert!(main.contains(".add_state::<AppState>()"));
+        assert!(main.contains(".add_system(setup_menu.in_schedule(OnEnter(AppState::Menu)))"));
+        assert!(main.contains(".add_system(menu.in_set(OnUpdate(AppState::Menu)))"));
+        assert!(main.contains(".add_system(cleanup_menu.in_schedule(OnExit(AppState::Menu)))"));
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn create_system_set_works() {
+        let mut scp = Scope::new();
+        scp.create_system_set(SystemSet { name: "MovementSet".to_string(), ..Default::default() });
+        assert_eq!(
+            scp.generate(),
+r#"#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
+pub struct MovementSet;
+"#
+        );
+    }
+
+    #[test]
+    fn generate_main_orders_systems() {
+        let in_set = |name: &str| System {
+            name: name.to_string(),
+            sets: vec!["GameplaySet".to_string()],
+            ..Default::default()
+        };
+        let bm = BevyModel {
+            system_sets: vec![SystemSet {
+                name: "GameplaySet".to_string(),
+                chain: true,
+                run_if: vec!["in_state(AppState::InGame)".to_string()],
+                ..Default::default()
+            }],
+            systems: vec![
+                in_set("input"),
+                in_set("movement"),
+                System {
+                    before: vec!["despawn".to_string()],
+                    ..in_set("collision")
+                },
+            ],
+            ..Default::default()
+        };
+        let main = bm
+            .generate_code(Scope::new(), GenerationType::Main)
+            .to_string();
+        assert!(main.contains(".configure_set(GameplaySet.run_if(in_state(AppState::InGame)))"));
+        assert!(main.contains(".add_system(input.in_set(GameplaySet))"));
+        assert!(main.contains(".add_system(movement.in_set(GameplaySet).after(input))"));
+        assert!(main.contains(
+            ".add_system(collision.in_set(GameplaySet).before(despawn).after(movement))"
+        ));
+    }
+
+    #[test]
+    fn generate_main_places_systems_in_schedules() {
+        let bm = BevyModel {
+            bevy_settings: Settings {
+                fixed_timestep: Some(Duration::from_millis(500)),
+                ..Default::default()
+            },
+            systems: vec![
+                System {
+                    name: "physics".to_string(),
+                    schedule: SystemSchedule::FixedUpdate,
+                    ..Default::default()
+                },
+                System {
+                    name: "sync_transforms".to_string(),
+                    schedule: SystemSchedule::PostUpdate,
+                    ..Default::default()
+                },
+                System {
+                    name: "gameplay".to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let main = bm
+            .generate_code(Scope::new(), GenerationType::Main)
+            .to_string();
+        assert!(main.contains(".insert_resource(FixedTime::new_from_secs(0.5))"));
+        assert!(main.contains(".add_system(physics.in_schedule(CoreSchedule::FixedUpdate))"));
+        assert!(main.contains(".add_system(sync_transforms.in_base_set(CoreSet::PostUpdate))"));
+        assert!(main.contains(".add_system(gameplay)"));
+    }
+
+    #[test]
+    fn generate_main_for_bevy_0_11() {
+        let bm = BevyModel {
+            bevy_settings: Settings {
+                bevy_version: BevyVersion::V0_11,
+                ..Default::default()
+            },
+            plugins: vec![
+                Plugin::default(),
+                Plugin {
+                    id: Default::default(),
+                    name: "EditorPlugin".to_string(),
+                    is_group: false,
+                    dependencies: vec![],
+                    members: vec![],
+                },
+            ],
+            system_sets: vec![
+                SystemSet {
+                    name: "GameplaySet".to_string(),
+                    run_if: vec!["in_state(AppState::InGame)".to_string()],
+                    ..Default::default()
+                },
+                SystemSet {
+                    name: "UiSet".to_string(),
+                    after: vec!["GameplaySet".to_string()],
+                    ..Default::default()
+                },
+            ],
+            events: vec![Event {
+                name: "Collision".to_string(),
+                ..Default::default()
+            }],
+            custom_assets: vec![CustomAsset {
+                name: "Level".to_string(),
+                ..Default::default()
+            }],
+            startup_systems: vec![System {
+                name: "setup".to_string(),
+                sets: vec!["UiSet".to_string()],
+                ..Default::default()
+            }],
+            systems: vec![
+                System {
+                    name: "setup_menu".to_string(),
+                    state: Some(StatePlacement::OnEnter("AppState::Menu".to_string())),
+                    ..Default::default()
+                },
+                System {
+                    name: "menu".to_string(),
+                    state: Some(StatePlacement::OnUpdate("AppState::Menu".to_string())),
+                    sets: vec!["UiSet".to_string()],
+                    ..Default::default()
+                },
+                System {
+                    name: "physics".to_string(),
+                    schedule: SystemSchedule::FixedUpdate,
+                    sets: vec!["GameplaySet".to_string()],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let main = bm
+            .generate_code(Scope::new(), GenerationType::Main)
+            .to_string();
+        assert!(main.contains(".add_plugins(DefaultPlugins)"));
+        assert!(main.contains(".add_plugins(EditorPlugin)"));
+        assert!(main.contains(
+            ".configure_set(FixedUpdate, GameplaySet.run_if(in_state(AppState::InGame)))"
+        ));
+        assert!(!main.contains(".configure_set(Update, GameplaySet"));
+        assert!(main.contains(
+            ".configure_set(Startup, UiSet.after(GameplaySet)).configure_set(Update, UiSet.after(GameplaySet))"
+        ));
+        assert!(main.contains(".add_systems(Startup, systems::setup.in_set(UiSet))"));
+        assert!(main.contains(".add_systems(OnEnter(AppState::Menu), setup_menu)"));
+        assert!(main
+            .contains(".add_systems(Update, menu.in_set(UiSet).run_if(in_state(AppState::Menu)))"));
+        assert!(main.contains(".add_systems(FixedUpdate, physics.in_set(GameplaySet))"));
+        assert!(!main.contains(".add_system("));
+
+        let components = bm
+            .generate_code(Scope::new(), GenerationType::Components)
+            .to_string();
+        assert!(components.contains("#[derive(Event)]\npub struct Collision"));
+        assert!(components.contains(
+            "#[derive(serde::Deserialize, bevy::reflect::TypeUuid, Debug, bevy::reflect::TypePath)]"
+        ));
+
+        let cargo = default_cargo_src_template(&bm);
+        assert!(cargo.contains("version = \"0.11\""));
+        assert!(cargo.contains("\"bevy/dynamic_linking\""));
+    }
 }
@@ -1,5 +1,14 @@
+pub mod assets;
+pub mod backend;
 pub mod bevy_model_template;
+pub mod diff;
 pub mod generate;
+pub mod merge;
 pub mod model;
 pub mod parse;
+pub mod scene;
+pub mod schema;
 pub mod templates;
+pub mod upgrade;
+pub mod usage;
+pub mod validate;
@@ -0,0 +1,302 @@
+use std::{collections::HashSet, fmt::Display};
+
+use crate::model::{BevyModel, ElementKey, Identified, ModelId};
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct MergeConflict {
+    // Element that was changed on both sides, e.g. `components.Player`
+    pub path: String,
+    pub message: String,
+}
+
+impl Display for MergeConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+// Conflicting elements keep our version in `model`
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct MergeResult {
+    pub model: BevyModel,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+impl BevyModel {
+    pub fn merge(base: &BevyModel, ours: &BevyModel, theirs: &BevyModel) -> MergeResult {
+        let mut conflicts = vec![];
+        macro_rules! elements {
+            ($field:ident) => {
+                merge_elements(
+                    stringify!($field),
+                    &base.$field,
+                    &ours.$field,
+                    &theirs.$field,
+                    |e| e.key(),
+                    |e| e.name(),
+                    &mut conflicts,
+                )
+            };
+        }
+        // Elements without an ID are matched by name
+        macro_rules! named {
+            ($field:ident, $($name:ident).+) => {
+                merge_elements(
+                    stringify!($field),
+                    &base.$field,
+                    &ours.$field,
+                    &theirs.$field,
+                    |e| ElementKey::Name(e.$($name).+.clone()),
+                    |e| e.$($name).+.as_str(),
+                    &mut conflicts,
+                )
+            };
+        }
+        macro_rules! value {
+            ($field:ident) => {
+                merge_value(
+                    stringify!($field),
+                    &base.$field,
+                    &ours.$field,
+                    &theirs.$field,
+                    &mut conflicts,
+                )
+            };
+        }
+
+        let mut model = BevyModel {
+            meta: value!(meta),
+            bevy_settings: value!(bevy_settings),
+            plugins: elements!(plugins),
+            components: elements!(components),
+            resources: elements!(resources),
+            bundles: elements!(bundles),
+            events: named!(events, name),
+            states: named!(states, name),
+            system_sets: named!(system_sets, name),
+            entities: named!(entities, name),
+            assets: named!(assets, name),
+            custom_assets: named!(custom_assets, name),
+            startup_systems: elements!(startup_systems),
+            systems: elements!(systems),
+            custom: elements!(custom),
+            imports: value!(imports),
+            examples: named!(examples, meta.name),
+            plugin_crates: named!(plugin_crates, meta.name),
+            last_id: ours.last_id.max(theirs.last_id),
+        };
+        // Elements added on both branches can share an ID, the later one gets a new one
+        let mut seen = HashSet::new();
+        for element in model.identified_mut() {
+            if !seen.insert(element.id()) {
+                element.set_id(ModelId::default());
+            }
+        }
+        model.assign_ids();
+
+        MergeResult { model, conflicts }
+    }
+}
+
+fn merge_value<T: PartialEq + Clone>(
+    path: &str,
+    base: &T,
+    ours: &T,
+    theirs: &T,
+    conflicts: &mut Vec<MergeConflict>,
+) -> T {
+    if ours == theirs || theirs == base {
+        ours.clone()
+    } else if ours == base {
+        theirs.clone()
+    } else {
+        conflicts.push(MergeConflict {
+            path: path.to_string(),
+            message: "changed on both sides".to_string(),
+        });
+        ours.clone()
+    }
+}
+
+// Keeps our order and appends elements only added by them
+fn merge_elements<T: PartialEq + Clone>(
+    kind: &str,
+    base: &[T],
+    ours: &[T],
+    theirs: &[T],
+    key: impl Fn(&T) -> ElementKey,
+    name: impl Fn(&T) -> &str,
+    conflicts: &mut Vec<MergeConflict>,
+) -> Vec<T> {
+    let find = |elements: &[T], k: &ElementKey| elements.iter().find(|e| key(e) == *k).cloned();
+    let path = |element: &T| format!("{kind}.{}", name(element));
+
+    let mut merged = vec![];
+    for our in ours {
+        let k = key(our);
+        match (find(base, &k), find(theirs, &k)) {
+            (None, None) => merged.push(our.clone()),
+            (None, Some(their)) if their == *our => merged.push(our.clone()),
+            (None, Some(their)) if name(&their) != name(our) => {
+                merged.push(our.clone());
+                merged.push(their);
+            }
+            (None, Some(_)) => {
+                conflicts.push(MergeConflict {
+                    path: path(our),
+                    message: "added differently on both sides".to_string(),
+                });
+                merged.push(our.clone());
+            }
+            (Some(base), None) if base == *our => (),
+            (Some(_), None) => {
+                conflicts.push(MergeConflict {
+                    path: path(our),
+                    message: "changed by us but removed by them".to_string(),
+                });
+                merged.push(our.clone());
+            }
+            (Some(base), Some(their)) => {
+                merged.push(merge_value(&path(our), &base, our, &their, conflicts));
+            }
+        }
+    }
+    for their in theirs {
+        let k = key(their);
+        if find(ours, &k).is_some() {
+            continue;
+        }
+        match find(base, &k) {
+            None => merged.push(their.clone()),
+            Some(base) if base == *their => (),
+            Some(_) => {
+                conflicts.push(MergeConflict {
+                    path: path(their),
+                    message: "removed by us but changed by them".to_string(),
+                });
+                merged.push(their.clone());
+            }
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::{Component, Event, System};
+
+    use super::*;
+
+    fn component(id: u64, name: &str) -> Component {
+        Component {
+            id: ModelId(id),
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn base() -> BevyModel {
+        BevyModel {
+            components: vec![component(1, "Player"), component(2, "Enemy")],
+            last_id: 2,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn independent_changes_merge_cleanly() {
+        let mut ours = base();
+        ours.components[0].is_reflected = false;
+        let mut theirs = base();
+        theirs.components.remove(1);
+        let id = theirs.new_id();
+        theirs.systems.push(System {
+            id,
+            name: "movement".to_string(),
+            ..Default::default()
+        });
+
+        let result = BevyModel::merge(&base(), &ours, &theirs);
+        assert!(result.conflicts.is_empty());
+        assert_eq!(vec![ours.components[0].clone()], result.model.components);
+        assert_eq!(theirs.systems, result.model.systems);
+    }
+
+    #[test]
+    fn renames_are_merged_by_id() {
+        let mut ours = base();
+        ours.components[0].name = "Hero".to_string();
+        let mut theirs = base();
+        theirs.components[0].is_reflected = false;
+        theirs.components[1].name = "Villain".to_string();
+
+        let result = BevyModel::merge(&base(), &ours, &theirs);
+        assert_eq!(
+            vec![MergeConflict {
+                path: "components.Hero".to_string(),
+                message: "changed on both sides".to_string(),
+            }],
+            result.conflicts
+        );
+        assert_eq!("Hero", result.model.components[0].name);
+        assert_eq!("Villain", result.model.components[1].name);
+    }
+
+    #[test]
+    fn change_and_removal_conflict() {
+        let mut ours = base();
+        ours.components[1].is_reflected = false;
+        let mut theirs = base();
+        theirs.components.remove(1);
+
+        let result = BevyModel::merge(&base(), &ours, &theirs);
+        assert_eq!(
+            vec!["components.Enemy: changed by us but removed by them".to_string()],
+            result
+                .conflicts
+                .iter()
+                .map(|c| c.to_string())
+                .collect::<Vec<String>>()
+        );
+        assert_eq!(ours.components, result.model.components);
+    }
+
+    #[test]
+    fn additions_on_both_sides_get_distinct_ids() {
+        let mut ours = base();
+        let id = ours.new_id();
+        ours.components.push(component(id.0, "Bullet"));
+        let mut theirs = base();
+        let id = theirs.new_id();
+        theirs.components.push(component(id.0, "Rocket"));
+
+        let result = BevyModel::merge(&base(), &ours, &theirs);
+        assert!(result.conflicts.is_empty());
+        let components = &result.model.components;
+        assert_eq!(
+            vec!["Player", "Enemy", "Bullet", "Rocket"],
+            components
+                .iter()
+                .map(|c| c.name.as_str())
+                .collect::<Vec<&str>>()
+        );
+        assert_eq!(ModelId(3), components[2].id);
+        assert_eq!(ModelId(4), components[3].id);
+    }
+
+    #[test]
+    fn elements_without_ids_merge_by_name() {
+        let event = |name: &str| Event {
+            name: name.to_string(),
+            ..Default::default()
+        };
+        let mut ours = base();
+        ours.events.push(event("Shoot"));
+        let mut theirs = base();
+        theirs.events.push(event("Explode"));
+
+        let result = BevyModel::merge(&base(), &ours, &theirs);
+        assert!(result.conflicts.is_empty());
+        assert_eq!(vec![event("Shoot"), event("Explode")], result.model.events);
+    }
+}
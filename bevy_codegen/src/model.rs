@@ -1,44 +1,248 @@
-use std::fmt::Display;
+use std::{fmt::Display, time::Duration};
 
-use bevy::prelude::Resource;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-use crate::parse::ParseBevyModel;
+use crate::{generate::snake_case, parse::ParseBevyModel};
 
-#[derive(Serialize, Deserialize, Clone, Default, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Default, Debug)]
 pub struct ReadPo2Version {
     pub po2_version: Po2Version,
     #[serde(skip_deserializing)]
     pub model: BevyModel,
 }
 
-#[derive(Serialize, Deserialize, Clone, Default, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Default, Debug)]
 pub struct ConfirmPo2Version {
     pub po2_version: Po2Version,
     pub model: BevyModel,
 }
 
-#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Default, Debug, Resource)]
+#[derive(
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    Clone,
+    Default,
+    Debug,
+    bevy::prelude::Resource,
+)]
 pub struct BevyModel {
     pub meta: Meta,
     pub bevy_settings: Settings,
     pub plugins: Vec<Plugin>,
     pub components: Vec<Component>,
+    #[serde(default)]
+    pub resources: Vec<Resource>,
+    #[serde(default)]
+    pub bundles: Vec<Bundle>,
+    #[serde(default)]
+    pub events: Vec<Event>,
+    #[serde(default)]
+    pub states: Vec<State>,
+    #[serde(default)]
+    pub system_sets: Vec<SystemSet>,
+    #[serde(default)]
+    pub entities: Vec<EntityPrefab>,
+    #[serde(default)]
+    pub assets: Vec<Asset>,
+    #[serde(default)]
+    pub custom_assets: Vec<CustomAsset>,
     pub startup_systems: Vec<System>,
     pub systems: Vec<System>,
     pub custom: Vec<Custom>,
     pub imports: Vec<Import>,
     pub examples: Vec<BevyModel>,
+    // Plugin or PluginGroup models generated as workspace member crates
+    #[serde(default)]
+    pub plugin_crates: Vec<BevyModel>,
+    // Last ModelId handed out by `new_id`
+    #[serde(default)]
+    pub last_id: u64,
+}
+
+// Stable identity of model elements across renames, 0 means not assigned yet
+#[derive(
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+)]
+#[serde(transparent)]
+pub struct ModelId(pub u64);
+
+impl ModelId {
+    pub fn is_assigned(&self) -> bool {
+        self.0 != 0
+    }
+}
+
+pub trait Identified {
+    fn id(&self) -> ModelId;
+    fn set_id(&mut self, id: ModelId);
+    fn name(&self) -> &str;
+    fn name_mut(&mut self) -> &mut String;
+
+    fn key(&self) -> ElementKey {
+        match self.id().is_assigned() {
+            true => ElementKey::Id(self.id()),
+            false => ElementKey::Name(self.name().to_string()),
+        }
+    }
+}
+
+// Elements are matched by ID, by name for files written before IDs existed
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum ElementKey {
+    Id(ModelId),
+    Name(String),
+}
+
+impl Identified for Component {
+    fn id(&self) -> ModelId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: ModelId) {
+        self.id = id;
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn name_mut(&mut self) -> &mut String {
+        &mut self.name
+    }
+}
+
+impl Identified for Bundle {
+    fn id(&self) -> ModelId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: ModelId) {
+        self.id = id;
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn name_mut(&mut self) -> &mut String {
+        &mut self.name
+    }
+}
+
+impl Identified for Resource {
+    fn id(&self) -> ModelId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: ModelId) {
+        self.id = id;
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn name_mut(&mut self) -> &mut String {
+        &mut self.name
+    }
+}
+
+impl Identified for System {
+    fn id(&self) -> ModelId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: ModelId) {
+        self.id = id;
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn name_mut(&mut self) -> &mut String {
+        &mut self.name
+    }
+}
+
+impl Identified for Plugin {
+    fn id(&self) -> ModelId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: ModelId) {
+        self.id = id;
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn name_mut(&mut self) -> &mut String {
+        &mut self.name
+    }
 }
 
-#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+impl Identified for CustomCode {
+    fn id(&self) -> ModelId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: ModelId) {
+        self.id = id;
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn name_mut(&mut self) -> &mut String {
+        &mut self.name
+    }
+}
+
+impl Identified for Custom {
+    fn id(&self) -> ModelId {
+        self.code().id
+    }
+
+    fn set_id(&mut self, id: ModelId) {
+        self.code_mut().id = id;
+    }
+
+    fn name(&self) -> &str {
+        &self.code().name
+    }
+
+    fn name_mut(&mut self) -> &mut String {
+        &mut self.code_mut().name
+    }
+}
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug)]
 pub enum BevyType {
     App,
     Plugin(String),
     PluginGroup(String),
     Example,
 }
-#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug)]
 pub struct Meta {
     pub name: String,
     pub bevy_type: BevyType,
@@ -57,12 +261,13 @@ impl Default for Meta {
     }
 }
 
-#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug, Default)]
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug, Default)]
 #[non_exhaustive]
 pub enum Po2Version {
+    V0_0_1 = 0,
     // Default points to the latest format version
     #[default]
-    V0_0_1 = 0,
+    V0_0_2 = 1,
 }
 
 impl Display for Po2Version {
@@ -75,6 +280,162 @@ impl Display for Po2Version {
     }
 }
 
+impl Po2Version {
+    pub fn next(&self) -> Option<Po2Version> {
+        match self {
+            Po2Version::V0_0_1 => Some(Po2Version::V0_0_2),
+            Po2Version::V0_0_2 => None,
+        }
+    }
+
+    // Converts a raw model written by the previous version to this one
+    fn upgrader(&self) -> Option<fn(&mut Value) -> serde_json::Result<()>> {
+        match self {
+            Po2Version::V0_0_1 => None,
+            Po2Version::V0_0_2 => Some(upgrade_to_0_0_2),
+        }
+    }
+}
+
+// Runs the upgraders up to the current version on a raw `.po2.json` file,
+// returns the version the file was written as if it had to be migrated. Entries that can't
+// be converted fail the whole migration, the file is left unchanged then
+pub fn migrate_po2(file: &mut Value) -> serde_json::Result<Option<Po2Version>> {
+    let written = serde_json::from_value::<Po2Version>(file["po2_version"].clone())?;
+    let mut version = written.clone();
+    let mut model = file["model"].clone();
+    while let Some(next) = version.next() {
+        if let Some(upgrader) = next.upgrader() {
+            upgrade_model(&mut model, upgrader)?;
+        }
+        version = next;
+    }
+    file["model"] = model;
+    if version == written {
+        return Ok(None);
+    }
+    let version = serde_json::to_value(version)?;
+    file["po2_version"] = version.clone();
+    set_model_version(&mut file["model"], &version);
+    Ok(Some(written))
+}
+
+// Examples and plugin crates are models of the same version
+fn upgrade_model(
+    model: &mut Value,
+    upgrader: fn(&mut Value) -> serde_json::Result<()>,
+) -> serde_json::Result<()> {
+    upgrader(model)?;
+    for children in ["examples", "plugin_crates"] {
+        if let Some(Value::Array(children)) = model.get_mut(children) {
+            for child in children {
+                upgrade_model(child, upgrader)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn set_model_version(model: &mut Value, version: &Value) {
+    if let Some(meta) = model.get_mut("meta") {
+        meta["po2_version"] = version.clone();
+    }
+    for children in ["examples", "plugin_crates"] {
+        if let Some(Value::Array(children)) = model.get_mut(children) {
+            children
+                .iter_mut()
+                .for_each(|c| set_model_version(c, version));
+        }
+    }
+}
+
+// `(name, type)` tuples became `SystemParam`s and `ComponentField`s
+fn upgrade_to_0_0_2(model: &mut Value) -> serde_json::Result<()> {
+    let tuples = |value: &Value| match value {
+        Value::Null => Ok(vec![]),
+        _ => serde_json::from_value::<Vec<(String, String)>>(value.clone()),
+    };
+    for systems in ["startup_systems", "systems"] {
+        let Some(Value::Array(systems)) = model.get_mut(systems) else {
+            continue;
+        };
+        for system in systems {
+            let params = tuples(&system["param"])?
+                .into_iter()
+                .map(|(name, ty)| SystemParam::from_tuple(&name, &ty))
+                .collect::<Vec<SystemParam>>();
+            system["param"] = serde_json::to_value(params)?;
+        }
+    }
+    if let Some(Value::Array(plugins)) = model.get_mut("plugins") {
+        for plugin in plugins {
+            let Some(config) = plugin["name"].as_str().map(str::to_string) else {
+                continue;
+            };
+            if let Some((name, members)) = plugin_members(&config) {
+                plugin["name"] = name.into();
+                plugin["members"] = serde_json::to_value(members)?;
+            }
+        }
+    }
+    if let Some(Value::Array(components)) = model.get_mut("components") {
+        for component in components {
+            let fields = tuples(&component["content"])?
+                .into_iter()
+                .map(|(name, ty)| ComponentField {
+                    name,
+                    ty,
+                    default: None,
+                })
+                .collect::<Vec<ComponentField>>();
+            component["content"] = serde_json::to_value(fields)?;
+        }
+    }
+    Ok(())
+}
+
+// Po2 0.0.1 configured groups in the name, e.g. `DefaultPlugins.set(AssetPlugin { .. })`,
+// None if the name has calls other than `set` and `disable`
+fn plugin_members(config: &str) -> Option<(String, Vec<PluginMember>)> {
+    let mut calls = split_top_level_at(config, '.').into_iter();
+    let name = calls.next()?;
+    let mut members: Vec<PluginMember> = vec![];
+    for call in calls {
+        let (member, value) =
+            if let Some(value) = call.strip_prefix("set(").and_then(|c| c.strip_suffix(')')) {
+                let value = value.trim();
+                let member = value
+                    .split(|c: char| c == '{' || c == '(' || c.is_whitespace())
+                    .next()
+                    .unwrap_or(value);
+                (member.to_string(), Some(value.to_string()))
+            } else if let Some(member) = call
+                .strip_prefix("disable::<")
+                .and_then(|c| c.strip_suffix(">()"))
+            {
+                (member.trim().to_string(), None)
+            } else {
+                return None;
+            };
+        let index = match members.iter().position(|m| m.name == member) {
+            Some(index) => index,
+            None => {
+                members.push(PluginMember {
+                    name: member,
+                    enabled: true,
+                    config: None,
+                });
+                members.len() - 1
+            }
+        };
+        match value {
+            Some(value) => members[index].config = Some(value),
+            None => members[index].enabled = false,
+        }
+    }
+    Some((name, members))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -100,68 +461,960 @@ mod tests {
 
     #[test]
     fn converts_to_the_correct_version() {
-        assert_eq!("0.0.1", Po2Version::V0_0_1.to_string())
+        assert_eq!("0.0.1", Po2Version::V0_0_1.to_string());
+        assert_eq!("0.0.2", Po2Version::V0_0_2.to_string())
+    }
+
+    #[test]
+    fn reads_0_0_1_param_tuples() {
+        assert_eq!(
+            SystemParam::Commands {
+                name: "commands".to_string()
+            },
+            SystemParam::from_tuple("mut commands", "Commands")
+        );
+        assert_eq!(
+            SystemParam::Res {
+                name: "keyboard_input".to_string(),
+                ty: "Input<KeyCode>".to_string()
+            },
+            SystemParam::from_tuple("keyboard_input", "Res<Input<KeyCode>>")
+        );
+        assert_eq!(
+            SystemParam::Query {
+                name: "query".to_string(),
+                data: vec![QueryData::Entity, QueryData::Mut("Transform".to_string())],
+                filter: vec![QueryFilter::With("Bullet".to_string())],
+            },
+            SystemParam::from_tuple("mut query", "Query<(Entity, &mut Transform), With<Bullet>>")
+        );
+        assert!(
+            SystemParam::from_tuple("mut query", "Query<(Entity, Option<&mut Velocity>)>").is_mut()
+        );
+        assert_eq!(
+            SystemParam::Custom {
+                name: "windows".to_string(),
+                ty: "NonSend<WinitWindows>".to_string(),
+                is_mut: false,
+            },
+            SystemParam::from_tuple("windows", "NonSend<WinitWindows>")
+        );
+    }
+
+    fn po2_0_0_1() -> Value {
+        let mut model = serde_json::to_value(BevyModel::default()).unwrap();
+        model["meta"]["po2_version"] = "V0_0_1".into();
+        model["components"] = serde_json::json!([
+            { "name": "Player", "content": [["speed", "f32"]], "is_reflected": true, "attributes": [], "derives": [] }
+        ]);
+        model["systems"] = serde_json::json!([
+            {
+                "name": "movement",
+                "param": [["time", "Res<Time>"], ["mut query", "Query<(&Player, &mut Transform)>"]],
+                "content": "",
+                "visibility": "pub",
+                "attributes": []
+            }
+        ]);
+        model["plugins"] = serde_json::json!([
+            {
+                "name": "DefaultPlugins.set(AssetPlugin{watch_for_changes: true, ..default()}).disable::<LogPlugin>()",
+                "is_group": true,
+                "dependencies": []
+            },
+            { "name": "EditorPlugin", "is_group": false, "dependencies": [] }
+        ]);
+        model["examples"] = serde_json::json!([model.clone()]);
+        serde_json::json!({ "po2_version": "V0_0_1", "model": model })
+    }
+
+    #[test]
+    fn migrates_0_0_1_to_0_0_2() {
+        let mut file = po2_0_0_1();
+        assert_eq!(Some(Po2Version::V0_0_1), migrate_po2(&mut file).unwrap());
+
+        let migrated = serde_json::from_value::<ConfirmPo2Version>(file).unwrap();
+        assert_eq!(Po2Version::V0_0_2, migrated.po2_version);
+        for model in [&migrated.model, &migrated.model.examples[0]] {
+            assert_eq!(Po2Version::V0_0_2, model.meta.po2_version);
+            assert_eq!("DefaultPlugins", model.plugins[0].name);
+            assert_eq!(
+                vec![
+                    PluginMember {
+                        name: "AssetPlugin".to_string(),
+                        enabled: true,
+                        config: Some(
+                            "AssetPlugin{watch_for_changes: true, ..default()}".to_string()
+                        ),
+                    },
+                    PluginMember {
+                        name: "LogPlugin".to_string(),
+                        enabled: false,
+                        config: None,
+                    },
+                ],
+                model.plugins[0].members
+            );
+            assert_eq!(
+                "DefaultPlugins.set(AssetPlugin{watch_for_changes: true, ..default()}).disable::<LogPlugin>()",
+                model.plugins[0].app_config()
+            );
+            assert!(model.plugins[1].members.is_empty());
+            assert!(model.validate().is_empty());
+            assert_eq!(
+                vec![ComponentField {
+                    name: "speed".to_string(),
+                    ty: "f32".to_string(),
+                    default: None,
+                }],
+                model.components[0].content
+            );
+            assert_eq!(
+                vec![
+                    SystemParam::Res {
+                        name: "time".to_string(),
+                        ty: "Time".to_string(),
+                    },
+                    SystemParam::Query {
+                        name: "query".to_string(),
+                        data: vec![
+                            QueryData::Ref("Player".to_string()),
+                            QueryData::Mut("Transform".to_string()),
+                        ],
+                        filter: vec![],
+                    },
+                ],
+                model.systems[0].param
+            );
+        }
+    }
+
+    #[test]
+    fn unreadable_0_0_1_entries_fail_the_migration() {
+        let mut file = po2_0_0_1();
+        file["model"]["systems"][0]["param"][1] = serde_json::json!(["mut query"]);
+        let unchanged = file.clone();
+        assert!(migrate_po2(&mut file).is_err());
+        assert_eq!(unchanged, file);
+    }
+
+    #[test]
+    fn current_version_is_not_migrated() {
+        let mut file = serde_json::to_value(ConfirmPo2Version::default()).unwrap();
+        let unchanged = file.clone();
+        assert_eq!(None, migrate_po2(&mut file).unwrap());
+        assert_eq!(unchanged, file);
+    }
+
+    #[test]
+    fn bundle_spawn_code() {
+        let bundle = Bundle {
+            name: "PlayerBundle".to_string(),
+            components: vec!["Player".to_string(), "SpriteBundle".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            "commands.spawn(PlayerBundle {\n    player: default(),\n    sprite_bundle: default(),\n});",
+            bundle.spawn_code()
+        );
+    }
+
+    #[test]
+    fn renders_system_params() {
+        let query = SystemParam::Query {
+            name: "query".to_string(),
+            data: vec![
+                QueryData::Entity,
+                QueryData::Ref("Player".to_string()),
+                QueryData::Mut("Transform".to_string()),
+            ],
+            filter: vec![QueryFilter::Without("Bullet".to_string())],
+        };
+        assert_eq!(
+            "Query<(Entity, &Player, &mut Transform), Without<Bullet>>",
+            query.ty()
+        );
+        assert!(query.is_mut());
+
+        let filtered = SystemParam::Query {
+            name: "query".to_string(),
+            data: vec![QueryData::Ref("Transform".to_string())],
+            filter: vec![
+                QueryFilter::With("Player".to_string()),
+                QueryFilter::Changed("Transform".to_string()),
+            ],
+        };
+        assert_eq!(
+            "Query<&Transform, (With<Player>, Changed<Transform>)>",
+            filtered.ty()
+        );
+        assert!(!filtered.is_mut());
+
+        let optional = SystemParam::Query {
+            name: "query".to_string(),
+            data: vec![
+                QueryData::Entity,
+                QueryData::Custom("Option<&mut Velocity>".to_string()),
+            ],
+            filter: vec![],
+        };
+        assert!(optional.is_mut());
+
+        let res = SystemParam::Res {
+            name: "time".to_string(),
+            ty: "Time".to_string(),
+        };
+        assert_eq!("Res<Time>", res.ty());
+        assert!(!res.is_mut());
+    }
+
+    #[test]
+    fn lists_component_access_of_system() {
+        let system = System {
+            param: vec![
+                SystemParam::Query {
+                    name: "players".to_string(),
+                    data: vec![
+                        QueryData::Ref("Player".to_string()),
+                        QueryData::Mut("Transform".to_string()),
+                    ],
+                    filter: vec![],
+                },
+                SystemParam::Query {
+                    name: "cam".to_string(),
+                    data: vec![QueryData::Ref("Camera".to_string())],
+                    filter: vec![],
+                },
+            ],
+            ..Default::default()
+        };
+        assert_eq!(vec!["Player", "Camera"], system.reads());
+        assert_eq!(vec!["Transform"], system.writes());
+    }
+
+    #[test]
+    fn assign_ids_keeps_existing_ids() {
+        let mut bm = BevyModel {
+            components: vec![
+                Component {
+                    id: ModelId(7),
+                    ..Default::default()
+                },
+                Component::default(),
+            ],
+            systems: vec![System::default()],
+            ..Default::default()
+        };
+        bm.assign_ids();
+        assert_eq!(ModelId(7), bm.components[0].id);
+        assert_eq!(ModelId(8), bm.components[1].id);
+        assert_eq!(ModelId(9), bm.systems[0].id);
+        assert_eq!(ModelId(10), bm.new_id());
     }
 }
 
-#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug)]
 pub struct System {
+    #[serde(default)]
+    pub id: ModelId,
     pub name: String,
-    pub param: Vec<(String, String)>,
+    pub param: Vec<SystemParam>,
     pub content: String,
     pub visibility: String,
     pub attributes: Vec<String>,
+    #[serde(default)]
+    pub state: Option<StatePlacement>,
+    #[serde(default)]
+    pub sets: Vec<String>,
+    #[serde(default)]
+    pub before: Vec<String>,
+    #[serde(default)]
+    pub after: Vec<String>,
+    #[serde(default)]
+    pub run_if: Vec<String>,
+    #[serde(default)]
+    pub schedule: SystemSchedule,
 }
 
 impl Default for System {
     fn default() -> Self {
         Self {
+            id: Default::default(),
             name: "test_system".to_string(),
             param: vec![],
             content: r#"println("Hello Bevy!")"#.to_string(),
             attributes: vec!["no_mangle".to_string()],
             visibility: "pub".to_string(),
+            state: None,
+            sets: vec![],
+            before: vec![],
+            after: vec![],
+            run_if: vec![],
+            schedule: Default::default(),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug, Default)]
+pub enum SystemSchedule {
+    First,
+    PreUpdate,
+    #[default]
+    Update,
+    PostUpdate,
+    Last,
+    // Runs on the fixed timestep set in `Settings::fixed_timestep`
+    FixedUpdate,
+}
+
+impl System {
+    // Components accessed immutably through queries
+    pub fn reads(&self) -> Vec<&str> {
+        self.query_data()
+            .filter_map(|d| match d {
+                QueryData::Ref(c) => Some(c.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    // Components accessed mutably through queries
+    pub fn writes(&self) -> Vec<&str> {
+        self.query_data()
+            .filter_map(|d| match d {
+                QueryData::Mut(c) => Some(c.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn query_data(&self) -> impl Iterator<Item = &QueryData> {
+        self.param.iter().flat_map(|p| match p {
+            SystemParam::Query { data, .. } => data.as_slice(),
+            _ => &[],
+        })
+    }
+}
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub enum SystemParam {
+    Commands {
+        name: String,
+    },
+    Query {
+        name: String,
+        data: Vec<QueryData>,
+        filter: Vec<QueryFilter>,
+    },
+    Res {
+        name: String,
+        ty: String,
+    },
+    ResMut {
+        name: String,
+        ty: String,
+    },
+    Local {
+        name: String,
+        ty: String,
+    },
+    EventReader {
+        name: String,
+        ty: String,
+    },
+    EventWriter {
+        name: String,
+        ty: String,
+    },
+    Custom {
+        name: String,
+        ty: String,
+        is_mut: bool,
+    },
+}
+
+impl SystemParam {
+    // Reads the `("mut query", "Query<&Transform, With<Player>>")` params of Po2 0.0.1
+    pub fn from_tuple(name: &str, ty: &str) -> SystemParam {
+        let (name, is_mut) = match name.trim().strip_prefix("mut ") {
+            Some(name) => (name.trim().to_string(), true),
+            None => (name.trim().to_string(), false),
+        };
+        let ty = ty.trim();
+        let (outer, inner) = match (ty.find('<'), ty.ends_with('>')) {
+            (Some(start), true) => (ty[..start].trim(), &ty[start + 1..ty.len() - 1]),
+            _ => (ty, ""),
+        };
+        let inner = inner.trim().to_string();
+        match outer.rsplit("::").next().unwrap_or(outer) {
+            "Commands" => SystemParam::Commands { name },
+            "Res" => SystemParam::Res { name, ty: inner },
+            "ResMut" => SystemParam::ResMut { name, ty: inner },
+            "Local" => SystemParam::Local { name, ty: inner },
+            "EventReader" => SystemParam::EventReader { name, ty: inner },
+            "EventWriter" => SystemParam::EventWriter { name, ty: inner },
+            "Query" => {
+                let mut args = split_top_level(&inner).into_iter();
+                let data = args.next().unwrap_or_default();
+                let filter = args.next().unwrap_or_default();
+                SystemParam::Query {
+                    name,
+                    data: tuple_items(&data)
+                        .iter()
+                        .map(|d| QueryData::from_ty(d))
+                        .collect(),
+                    filter: tuple_items(&filter)
+                        .iter()
+                        .map(|f| QueryFilter::from_ty(f))
+                        .collect(),
+                }
+            }
+            _ => SystemParam::Custom {
+                name,
+                ty: ty.to_string(),
+                is_mut,
+            },
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            SystemParam::Commands { name }
+            | SystemParam::Query { name, .. }
+            | SystemParam::Res { name, .. }
+            | SystemParam::ResMut { name, .. }
+            | SystemParam::Local { name, .. }
+            | SystemParam::EventReader { name, .. }
+            | SystemParam::EventWriter { name, .. }
+            | SystemParam::Custom { name, .. } => name,
+        }
+    }
+
+    // Whether the binding has to be declared `mut`
+    pub fn is_mut(&self) -> bool {
+        match self {
+            SystemParam::Res { .. } => false,
+            SystemParam::Query { data, .. } => data.iter().any(QueryData::is_mut),
+            SystemParam::Custom { is_mut, .. } => *is_mut,
+            _ => true,
+        }
+    }
+
+    pub fn ty(&self) -> String {
+        match self {
+            SystemParam::Commands { .. } => "Commands".to_string(),
+            SystemParam::Query { data, filter, .. } => {
+                let data = tuple_format(data.iter().map(QueryData::ty).collect());
+                if filter.is_empty() {
+                    format!("Query<{data}>")
+                } else {
+                    let filter = tuple_format(filter.iter().map(QueryFilter::ty).collect());
+                    format!("Query<{data}, {filter}>")
+                }
+            }
+            SystemParam::Res { ty, .. } => format!("Res<{ty}>"),
+            SystemParam::ResMut { ty, .. } => format!("ResMut<{ty}>"),
+            SystemParam::Local { ty, .. } => format!("Local<{ty}>"),
+            SystemParam::EventReader { ty, .. } => format!("EventReader<{ty}>"),
+            SystemParam::EventWriter { ty, .. } => format!("EventWriter<{ty}>"),
+            SystemParam::Custom { ty, .. } => ty.clone(),
+        }
+    }
+}
+
+// Splits at commas outside of `<..>`, `(..)` and `{..}`
+fn split_top_level(items: &str) -> Vec<String> {
+    split_top_level_at(items, ',')
+}
+
+fn split_top_level_at(items: &str, separator: char) -> Vec<String> {
+    let mut parts = vec![];
+    let mut depth = 0;
+    let mut part = String::new();
+    for c in items.chars() {
+        match c {
+            '<' | '(' | '{' => depth += 1,
+            '>' | ')' | '}' => depth -= 1,
+            c if c == separator && depth == 0 => {
+                parts.push(part.trim().to_string());
+                part.clear();
+                continue;
+            }
+            _ => (),
+        }
+        part.push(c);
+    }
+    if !part.trim().is_empty() {
+        parts.push(part.trim().to_string());
+    }
+    parts
+}
+
+fn tuple_items(ty: &str) -> Vec<String> {
+    match ty.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+        Some(items) => split_top_level(items),
+        None if ty.is_empty() => vec![],
+        None => vec![ty.to_string()],
+    }
+}
+
+fn tuple_format(items: Vec<String>) -> String {
+    if items.len() == 1 {
+        items[0].clone()
+    } else {
+        format!("({})", items.join(", "))
+    }
+}
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub enum QueryData {
+    Entity,
+    Ref(String),
+    Mut(String),
+    Custom(String),
+}
+
+impl QueryData {
+    pub fn from_ty(ty: &str) -> QueryData {
+        if ty == "Entity" {
+            QueryData::Entity
+        } else if let Some(c) = ty.strip_prefix("&mut ") {
+            QueryData::Mut(c.trim().to_string())
+        } else if let Some(c) = ty.strip_prefix('&') {
+            QueryData::Ref(c.trim().to_string())
+        } else {
+            QueryData::Custom(ty.to_string())
+        }
+    }
+
+    // Custom data like `Option<&mut Velocity>` borrows mutably as well
+    pub fn is_mut(&self) -> bool {
+        match self {
+            QueryData::Mut(_) => true,
+            QueryData::Custom(ty) => ty.replace(char::is_whitespace, "").contains("&mut"),
+            _ => false,
+        }
+    }
+
+    pub fn ty(&self) -> String {
+        match self {
+            QueryData::Entity => "Entity".to_string(),
+            QueryData::Ref(c) => format!("&{c}"),
+            QueryData::Mut(c) => format!("&mut {c}"),
+            QueryData::Custom(ty) => ty.clone(),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub enum QueryFilter {
+    With(String),
+    Without(String),
+    Added(String),
+    Changed(String),
+    Custom(String),
+}
+
+impl QueryFilter {
+    pub fn from_ty(ty: &str) -> QueryFilter {
+        let component = |prefix: &str| {
+            ty.strip_prefix(prefix)
+                .and_then(|c| c.strip_suffix('>'))
+                .map(|c| c.trim().to_string())
+        };
+        if let Some(c) = component("With<") {
+            QueryFilter::With(c)
+        } else if let Some(c) = component("Without<") {
+            QueryFilter::Without(c)
+        } else if let Some(c) = component("Added<") {
+            QueryFilter::Added(c)
+        } else if let Some(c) = component("Changed<") {
+            QueryFilter::Changed(c)
+        } else {
+            QueryFilter::Custom(ty.to_string())
+        }
+    }
+
+    pub fn ty(&self) -> String {
+        match self {
+            QueryFilter::With(c) => format!("With<{c}>"),
+            QueryFilter::Without(c) => format!("Without<{c}>"),
+            QueryFilter::Added(c) => format!("Added<{c}>"),
+            QueryFilter::Changed(c) => format!("Changed<{c}>"),
+            QueryFilter::Custom(ty) => ty.clone(),
+        }
+    }
+}
+
+// Runs the system relative to a state value, e.g. `OnEnter("AppState::Menu")`
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub enum StatePlacement {
+    OnEnter(String),
+    OnExit(String),
+    OnUpdate(String),
+}
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct SystemSet {
+    pub name: String,
+    // Member systems run in the order they are declared
+    pub chain: bool,
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+    pub run_if: Vec<String>,
+}
+
+impl Default for SystemSet {
+    fn default() -> Self {
+        Self {
+            name: "TestSet".to_string(),
+            chain: false,
+            before: vec![],
+            after: vec![],
+            run_if: vec![],
         }
     }
 }
 
-#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct State {
+    pub name: String,
+    pub variants: Vec<String>,
+    pub initial: String,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            name: "AppState".to_string(),
+            variants: vec!["Menu".to_string(), "InGame".to_string()],
+            initial: "Menu".to_string(),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug)]
 pub struct Component {
+    #[serde(default)]
+    pub id: ModelId,
     pub name: String,
-    pub content: Vec<(String, String)>,
+    pub content: Vec<ComponentField>,
     pub is_reflected: bool,
     pub attributes: Vec<String>,
     pub derives: Vec<String>,
+    #[serde(default)]
+    pub shape: ComponentShape,
 }
 
 impl Default for Component {
     fn default() -> Self {
         Self {
+            id: Default::default(),
             name: "TestComponent".to_string(),
             content: Default::default(),
             attributes: Default::default(),
             derives: Default::default(),
             is_reflected: true,
+            shape: Default::default(),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct ComponentField {
+    pub name: String,
+    pub ty: String,
+    // Expression used in the generated `Default` impl, `Default::default()` when not set
+    #[serde(default)]
+    pub default: Option<String>,
+}
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug, Default)]
+pub enum ComponentShape {
+    // Named fields taken from `Component::content`
+    #[default]
+    Struct,
+    // Field types of a tuple struct, e.g. `Health(f32)`
+    // Defaults are read from `Component::content` entries named by field index
+    Tuple(Vec<String>),
+    Unit,
+    Enum(Vec<ComponentVariant>),
+}
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct ComponentVariant {
+    pub name: String,
+    // Tuple field types, empty for unit variants
+    pub fields: Vec<String>,
+}
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct EntityPrefab {
+    pub name: String,
+    pub components: Vec<ComponentInstance>,
+    pub children: Vec<EntityPrefab>,
+}
+
+impl Default for EntityPrefab {
+    fn default() -> Self {
+        Self {
+            name: "TestEntity".to_string(),
+            components: Default::default(),
+            children: Default::default(),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct Asset {
+    // Field name in the generated handles resource
+    pub name: String,
+    // Relative to `Meta.asset_path`
+    pub path: String,
+    pub kind: AssetKind,
+}
+
+impl Default for Asset {
+    fn default() -> Self {
+        Self {
+            name: "test_asset".to_string(),
+            path: "test_asset.png".to_string(),
+            kind: Default::default(),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug, Default)]
+pub enum AssetKind {
+    #[default]
+    Image,
+    Audio,
+    Font,
+    // Also used for glTF files, e.g. `ship.glb#Scene0`
+    Scene,
+    Custom(String),
+}
+
+impl AssetKind {
+    pub fn ty(&self) -> &str {
+        match self {
+            AssetKind::Image => "Image",
+            AssetKind::Audio => "AudioSource",
+            AssetKind::Font => "Font",
+            AssetKind::Scene => "Scene",
+            AssetKind::Custom(ty) => ty,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct CustomAsset {
+    pub name: String,
+    // Used for `#[uuid = "..."]`, has to be unique per asset type
+    pub uuid: String,
+    pub content: Vec<(String, String)>,
+    // Without the leading dot, e.g. `level.ron`
+    pub extensions: Vec<String>,
+    pub format: AssetFormat,
+}
+
+impl Default for CustomAsset {
+    fn default() -> Self {
+        Self {
+            name: "TestAsset".to_string(),
+            uuid: "39cadc56-aa9c-4543-8640-a018b74b5052".to_string(),
+            content: Default::default(),
+            extensions: vec!["test.ron".to_string()],
+            format: Default::default(),
+        }
+    }
+}
+
+impl CustomAsset {
+    pub fn loader_name(&self) -> String {
+        format!("{}Loader", self.name)
+    }
+}
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug, Default)]
+pub enum AssetFormat {
+    #[default]
+    Ron,
+    Json,
+}
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug, Default)]
+pub struct ComponentInstance {
+    pub name: String,
+    // Field name and value pairs, tuple fields are named by their index
+    pub fields: Vec<(String, String)>,
+    // Full expression used instead of the fields, e.g. `Team::Red`, not written to scene files
+    pub value: Option<String>,
+}
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct Bundle {
+    #[serde(default)]
+    pub id: ModelId,
+    pub name: String,
+    // Component names, each becomes a field named after the component in snake_case
+    pub components: Vec<String>,
+    pub attributes: Vec<String>,
+    pub derives: Vec<String>,
+}
+
+impl Bundle {
+    // `commands.spawn(..)` snippet for system content, every member starts at `default()`
+    pub fn spawn_code(&self) -> String {
+        let fields = self
+            .components
+            .iter()
+            .map(|c| format!("    {}: default(),\n", snake_case(c)))
+            .collect::<String>();
+        format!("commands.spawn({} {{\n{fields}}});", self.name)
+    }
+}
+
+impl Default for Bundle {
+    fn default() -> Self {
+        Self {
+            id: Default::default(),
+            name: "TestBundle".to_string(),
+            components: Default::default(),
+            attributes: Default::default(),
+            derives: Default::default(),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct Resource {
+    #[serde(default)]
+    pub id: ModelId,
+    pub name: String,
+    pub content: Vec<(String, String)>,
+    pub is_reflected: bool,
+    pub attributes: Vec<String>,
+    pub derives: Vec<String>,
+    pub init: ResourceInit,
+    // Defined outside of the components crate (e.g. bevy's `Msaa`), only registered on the app
+    pub is_external: bool,
+}
+
+impl Default for Resource {
+    fn default() -> Self {
+        Self {
+            id: Default::default(),
+            name: "TestResource".to_string(),
+            content: Default::default(),
+            is_reflected: true,
+            attributes: Default::default(),
+            derives: Default::default(),
+            init: Default::default(),
+            is_external: false,
         }
     }
 }
 
-#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug, Default)]
+pub enum ResourceInit {
+    // `init_resource::<T>()`, requires `Default` or `FromWorld`
+    #[default]
+    Init,
+    // `insert_resource(<value>)`
+    Insert(String),
+}
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct Event {
+    pub name: String,
+    pub content: Vec<(String, String)>,
+    pub attributes: Vec<String>,
+    pub derives: Vec<String>,
+}
+
+impl Default for Event {
+    fn default() -> Self {
+        Self {
+            name: "TestEvent".to_string(),
+            content: Default::default(),
+            attributes: Default::default(),
+            derives: Default::default(),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug)]
 pub struct Plugin {
+    #[serde(default)]
+    pub id: ModelId,
     pub name: String,
     pub is_group: bool,
     pub dependencies: Vec<CargoDependency>,
+    // Only used for groups
+    #[serde(default)]
+    pub members: Vec<PluginMember>,
+}
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug)]
+pub struct PluginMember {
+    pub name: String,
+    pub enabled: bool,
+    // Plugin value used with `set(...)`, e.g. `WindowPlugin { ..default() }`
+    pub config: Option<String>,
+}
+
+impl Default for PluginMember {
+    fn default() -> Self {
+        Self {
+            name: "TestPlugin".to_string(),
+            enabled: true,
+            config: None,
+        }
+    }
 }
 
-#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+impl Plugin {
+    // Group with the `set`/`disable` configuration of its members
+    pub fn app_config(&self) -> String {
+        let mut config = self.name.clone();
+        for member in &self.members {
+            if let Some(value) = &member.config {
+                config.push_str(&format!(".set({value})"));
+            }
+            if !member.enabled {
+                config.push_str(&format!(".disable::<{}>()", member.name));
+            }
+        }
+        config
+    }
+}
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug)]
 pub enum Custom {
     Main(CustomCode),
     Component(CustomCode),
     System(CustomCode),
 }
 
-#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+impl Custom {
+    pub fn code(&self) -> &CustomCode {
+        match self {
+            Custom::Main(code) | Custom::Component(code) | Custom::System(code) => code,
+        }
+    }
+
+    pub fn code_mut(&mut self) -> &mut CustomCode {
+        match self {
+            Custom::Main(code) | Custom::Component(code) | Custom::System(code) => code,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug)]
 pub struct CustomCode {
+    #[serde(default)]
+    pub id: ModelId,
     pub name: String,
     pub content: String,
 }
@@ -169,14 +1422,16 @@ pub struct CustomCode {
 impl Default for Plugin {
     fn default() -> Self {
         Self {
+            id: Default::default(),
             name: "DefaultPlugins".to_string(),
             is_group: true,
             dependencies: Default::default(),
+            members: Default::default(),
         }
     }
 }
 
-#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Default, Debug)]
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Default, Debug)]
 pub struct CargoDependency {
     pub name: String,
     pub dependency_type: DependencyType,
@@ -184,7 +1439,7 @@ pub struct CargoDependency {
     pub features: Vec<String>,
 }
 
-#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug)]
 pub enum DependencyType {
     Crate(String),
     Git(String, Option<String>, Option<String>),
@@ -205,7 +1460,7 @@ impl Display for DependencyType {
     }
 }
 
-#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug, Default)]
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug, Default)]
 pub enum Used {
     #[default]
     Main,
@@ -213,19 +1468,56 @@ pub enum Used {
     Systems,
 }
 
-#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Default, Debug)]
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Default, Debug)]
 pub struct Import {
     pub used: Used,
     pub dependency: CargoDependency,
 }
 
-#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Default, Debug)]
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Default, Debug)]
 pub struct Settings {
     pub features: Vec<Feature>,
     pub dev_features: Vec<Feature>,
+    #[serde(default)]
+    pub fixed_timestep: Option<Duration>,
+    // Also write the entities to `assets/<name>.scn.ron`
+    #[serde(default)]
+    pub export_scene: bool,
+    #[serde(default)]
+    pub bevy_version: BevyVersion,
+}
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Copy, Debug, Default)]
+pub enum BevyVersion {
+    #[default]
+    V0_10,
+    V0_11,
+}
+
+impl BevyVersion {
+    pub fn to_version(&self) -> &str {
+        match self {
+            BevyVersion::V0_10 => "0.10",
+            BevyVersion::V0_11 => "0.11",
+        }
+    }
+
+    pub fn next(&self) -> Option<BevyVersion> {
+        match self {
+            BevyVersion::V0_10 => Some(BevyVersion::V0_11),
+            BevyVersion::V0_11 => None,
+        }
+    }
+
+    pub fn dynamic_feature(&self) -> Feature {
+        match self {
+            BevyVersion::V0_10 => Feature::Dynamic,
+            BevyVersion::V0_11 => Feature::DynamicLinking,
+        }
+    }
 }
 
-#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+#[derive(PartialEq, Eq, Serialize, Deserialize, JsonSchema, Clone, Debug)]
 pub enum Feature {
     Default,
     BevyAudio,
@@ -243,7 +1535,9 @@ pub enum Feature {
     WgpuTrace,
     BevyCiTesting,
     BevySprite,
+    // Renamed to DynamicLinking in Bevy 0.11
     Dynamic,
+    DynamicLinking,
     BevyUi,
     Tga,
     Serialize,
@@ -251,6 +1545,7 @@ pub enum Feature {
     BevyCorePipeline,
     Wav,
     Trace,
+    // Removed in Bevy 0.11
     SubpixelGlyphAtlas,
     Bmp,
     BevyGltf,
@@ -285,6 +1580,7 @@ impl Feature {
             Feature::BevyCiTesting => "bevy_ci_testing",
             Feature::BevySprite => "bevy_sprite",
             Feature::Dynamic => "dynamic",
+            Feature::DynamicLinking => "dynamic_linking",
             Feature::BevyUi => "bevy_ui",
             Feature::Tga => "tga",
             Feature::Serialize => "serialize",
@@ -308,6 +1604,93 @@ impl Feature {
     }
 }
 
+impl BevyModel {
+    pub fn new_id(&mut self) -> ModelId {
+        self.last_id += 1;
+        ModelId(self.last_id)
+    }
+
+    // Gives every element without an ID one, e.g. after loading an older file
+    pub fn assign_ids(&mut self) {
+        let last_id = self
+            .identified()
+            .map(|e| e.id().0)
+            .max()
+            .unwrap_or_default();
+        self.last_id = self.last_id.max(last_id);
+        let mut next_id = self.last_id;
+        for element in self.identified_mut().filter(|e| !e.id().is_assigned()) {
+            next_id += 1;
+            element.set_id(ModelId(next_id));
+        }
+        self.last_id = next_id;
+    }
+
+    pub fn identified(&self) -> impl Iterator<Item = &dyn Identified> {
+        let custom = self.custom.iter().map(|c| match c {
+            Custom::Main(code) | Custom::Component(code) | Custom::System(code) => code,
+        });
+        self.components
+            .iter()
+            .map(|e| e as &dyn Identified)
+            .chain(self.bundles.iter().map(|e| e as &dyn Identified))
+            .chain(self.resources.iter().map(|e| e as &dyn Identified))
+            .chain(self.startup_systems.iter().map(|e| e as &dyn Identified))
+            .chain(self.systems.iter().map(|e| e as &dyn Identified))
+            .chain(self.plugins.iter().map(|e| e as &dyn Identified))
+            .chain(custom.map(|e| e as &dyn Identified))
+    }
+
+    pub fn identified_mut(&mut self) -> impl Iterator<Item = &mut dyn Identified> {
+        let custom = self.custom.iter_mut().map(|c| match c {
+            Custom::Main(code) | Custom::Component(code) | Custom::System(code) => code,
+        });
+        self.components
+            .iter_mut()
+            .map(|e| e as &mut dyn Identified)
+            .chain(self.bundles.iter_mut().map(|e| e as &mut dyn Identified))
+            .chain(self.resources.iter_mut().map(|e| e as &mut dyn Identified))
+            .chain(
+                self.startup_systems
+                    .iter_mut()
+                    .map(|e| e as &mut dyn Identified),
+            )
+            .chain(self.systems.iter_mut().map(|e| e as &mut dyn Identified))
+            .chain(self.plugins.iter_mut().map(|e| e as &mut dyn Identified))
+            .chain(custom.map(|e| e as &mut dyn Identified))
+    }
+
+    pub fn crate_name(&self) -> String {
+        self.meta.name.replace('-', "_")
+    }
+
+    // Plugin added to the parent app, None if this isn't a plugin model
+    pub fn crate_plugin(&self) -> Option<Plugin> {
+        let (name, is_group) = match &self.meta.bevy_type {
+            BevyType::Plugin(name) => (name, false),
+            BevyType::PluginGroup(name) => (name, true),
+            _ => return None,
+        };
+        Some(Plugin {
+            id: Default::default(),
+            name: format!("{}::{name}", self.crate_name()),
+            is_group,
+            dependencies: vec![],
+            members: vec![],
+        })
+    }
+
+    // Nested plugin crates are flattened into the root workspace
+    pub fn all_plugin_crates(&self) -> Vec<&BevyModel> {
+        let mut crates = vec![];
+        for plugin_crate in &self.plugin_crates {
+            crates.push(plugin_crate);
+            crates.extend(plugin_crate.all_plugin_crates());
+        }
+        crates
+    }
+}
+
 impl Display for BevyModel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let _ = writeln!(f, "BevyModel:");
@@ -323,6 +1706,62 @@ impl Display for BevyModel {
 
         let _ = writeln!(f);
 
+        let _ = writeln!(f, "   Bundles:");
+        self.bundles.iter().for_each(|d| {
+            let _ = writeln!(f, "       {}", d.name);
+        });
+
+        let _ = writeln!(f);
+
+        let _ = writeln!(f, "   Resources:");
+        self.resources.iter().for_each(|d| {
+            let _ = writeln!(f, "       {}", d.name);
+        });
+
+        let _ = writeln!(f);
+
+        let _ = writeln!(f, "   Events:");
+        self.events.iter().for_each(|d| {
+            let _ = writeln!(f, "       {}", d.name);
+        });
+
+        let _ = writeln!(f);
+
+        let _ = writeln!(f, "   States:");
+        self.states.iter().for_each(|d| {
+            let _ = writeln!(f, "       {}", d.name);
+        });
+
+        let _ = writeln!(f);
+
+        let _ = writeln!(f, "   System Sets:");
+        self.system_sets.iter().for_each(|d| {
+            let _ = writeln!(f, "       {}", d.name);
+        });
+
+        let _ = writeln!(f);
+
+        let _ = writeln!(f, "   Entities:");
+        self.entities.iter().for_each(|d| {
+            let _ = writeln!(f, "       {}", d.name);
+        });
+
+        let _ = writeln!(f);
+
+        let _ = writeln!(f, "   Assets:");
+        self.assets.iter().for_each(|d| {
+            let _ = writeln!(f, "       {}: {}", d.name, d.path);
+        });
+
+        let _ = writeln!(f);
+
+        let _ = writeln!(f, "   Custom Assets:");
+        self.custom_assets.iter().for_each(|d| {
+            let _ = writeln!(f, "       {}", d.name);
+        });
+
+        let _ = writeln!(f);
+
         let _ = writeln!(f, "   Startup Systems:");
         self.startup_systems.iter().for_each(|d| {
             let _ = writeln!(f, "       {}", d.name);
@@ -342,6 +1781,13 @@ impl Display for BevyModel {
             let _ = writeln!(f, "       {d:?},");
         });
 
+        let _ = writeln!(f);
+
+        let _ = writeln!(f, "   Plugin Crates:");
+        self.plugin_crates.iter().for_each(|d| {
+            let _ = writeln!(f, "       {},", d.meta.name);
+        });
+
         Ok(())
     }
 }
@@ -360,8 +1806,19 @@ impl From<ParseBevyModel> for BevyModel {
                 ..Default::default()
             }
         });
+        let events = value.events.into_iter().map(|name| Event {
+            name,
+            ..Default::default()
+        });
+        // Registered types are generated from the reflect flag
+        let components = value.components.into_iter().map(|mut c| {
+            c.is_reflected |= value.registered_types.contains(&c.name);
+            c
+        });
         BevyModel {
             imports: imports.collect(),
+            components: components.collect(),
+            events: events.collect(),
             ..Default::default()
         }
     }
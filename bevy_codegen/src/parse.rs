@@ -1,4 +1,7 @@
-use syn::{Expr, UseGroup, UseTree};
+use quote::ToTokens;
+use syn::{Attribute, Expr, Fields, UseGroup, UseTree};
+
+use crate::model::{Component, ComponentField, ComponentShape, ComponentVariant};
 
 pub fn parse_file(file: syn::File) -> Option<ParseBevyModel> {
     //println!("all:\n{file:#?}");
@@ -11,10 +14,14 @@ pub fn parse_file(file: syn::File) -> Option<ParseBevyModel> {
                     //println!("main:\n{x:#?}");
                     let mut r = parse_fn(ParseBevyModel::default(), Box::new(x.clone()));
                     r.app_builder.reverse();
+                    r.events.reverse();
+                    r.registered_types.reverse();
                     //println!("{:?}", r.app_builder);
                     pbm.app_builder = r.app_builder;
+                    pbm.events = r.events;
+                    pbm.registered_types = r.registered_types;
                 }
-            },
+            }
             syn::Item::Fn(fn_item) => {
                 // Added to system struct (Not defined yet, see generate.rs)
                 let a = format!("{:?}", fn_item);
@@ -25,6 +32,49 @@ pub fn parse_file(file: syn::File) -> Option<ParseBevyModel> {
                 //println!("Use: {rs:?}");
                 imports.push(rs);
             }
+            syn::Item::Struct(struct_item) => {
+                let (content, shape) = match struct_item.fields {
+                    Fields::Named(x) => {
+                        let content = x.named.into_iter().filter_map(|f| {
+                            let ty = token_string(f.ty);
+                            f.ident.map(|ident| ComponentField {
+                                name: ident.to_string(),
+                                ty,
+                                default: None,
+                            })
+                        });
+                        (content.collect(), ComponentShape::Struct)
+                    }
+                    Fields::Unnamed(x) => {
+                        let types = x.unnamed.into_iter().map(|f| token_string(f.ty));
+                        (vec![], ComponentShape::Tuple(types.collect()))
+                    }
+                    Fields::Unit => (vec![], ComponentShape::Unit),
+                };
+                let component = Component {
+                    name: struct_item.ident.to_string(),
+                    content,
+                    shape,
+                    ..Default::default()
+                };
+                if let Some(component) = parse_component_attributes(component, struct_item.attrs) {
+                    pbm.components.push(component);
+                }
+            }
+            syn::Item::Enum(enum_item) => {
+                let variants = enum_item.variants.into_iter().map(|v| ComponentVariant {
+                    name: v.ident.to_string(),
+                    fields: v.fields.into_iter().map(|f| token_string(f.ty)).collect(),
+                });
+                let component = Component {
+                    name: enum_item.ident.to_string(),
+                    shape: ComponentShape::Enum(variants.collect()),
+                    ..Default::default()
+                };
+                if let Some(component) = parse_component_attributes(component, enum_item.attrs) {
+                    pbm.components.push(component);
+                }
+            }
             _ => (),
         }
     }
@@ -38,6 +88,61 @@ pub fn parse_file(file: syn::File) -> Option<ParseBevyModel> {
     }
 }
 
+// Only items deriving `Component` are kept, the derives and attributes generated from
+// `is_reflected` are folded back into the flag
+fn parse_component_attributes(
+    mut component: Component,
+    attrs: Vec<Attribute>,
+) -> Option<Component> {
+    let mut derives = vec![];
+    let mut attributes = vec![];
+    for attr in attrs {
+        if attr.path().is_ident("derive") {
+            let _ = attr.parse_nested_meta(|meta| {
+                if let Some(segment) = meta.path.segments.last() {
+                    derives.push(segment.ident.to_string());
+                }
+                Ok(())
+            });
+        } else {
+            attributes.push(token_string(attr.meta));
+        }
+    }
+
+    if !derives.iter().any(|d| d.eq("Component")) {
+        return None;
+    }
+
+    component.is_reflected = derives.iter().any(|d| d.eq("Reflect"));
+    derives.retain(|d| {
+        !(d.eq("Component") || (component.is_reflected && (d.eq("Reflect") || d.eq("Default"))))
+    });
+    if component.is_reflected {
+        attributes.retain(|a| a.ne("reflect(Component)"));
+    }
+    component.derives = derives;
+    component.attributes = attributes;
+    Some(component)
+}
+
+// Tokens are printed with spaces between all tokens, only keep those between words and after commas
+fn token_string(tokens: impl ToTokens) -> String {
+    let raw = tokens.to_token_stream().to_string();
+    let chars = raw.chars().collect::<Vec<char>>();
+    let is_word = |c: &char| c.is_alphanumeric() || c.eq(&'_');
+    let mut res = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        if c.eq(&' ') && i > 0 && i + 1 < chars.len() {
+            let (prev, next) = (&chars[i - 1], &chars[i + 1]);
+            if !(prev.eq(&',') || (is_word(prev) && is_word(next))) {
+                continue;
+            }
+        }
+        res.push(*c);
+    }
+    res
+}
+
 fn parse_use(mut imports: Vec<String>, use_tree: UseTree) -> Vec<String> {
     let res = match use_tree {
         syn::UseTree::Path(x) => {
@@ -93,6 +198,9 @@ fn parse_group(group: UseGroup) -> Vec<String> {
 pub struct ParseBevyModel {
     pub imports: Vec<String>,
     pub app_builder: Vec<(String, String)>,
+    pub events: Vec<String>,
+    pub registered_types: Vec<String>,
+    pub components: Vec<Component>,
     pub code: Vec<String>,
 }
 
@@ -170,6 +278,11 @@ fn parse_fn(mut init_app_builder: ParseBevyModel, expr: Box<Expr>) -> ParseBevyM
                 }
                 None => None,
             };
+            match (&tf, x.method.to_string().as_str()) {
+                (Some(event), "add_event") => init_app_builder.events.push(event.clone()),
+                (Some(ty), "register_type") => init_app_builder.registered_types.push(ty.clone()),
+                _ => (),
+            }
             let method = match tf {
                 Some(y) => x.method.to_string() + "::<" + &y + ">",
                 None => x.method.to_string(),
@@ -187,6 +300,10 @@ fn parse_fn(mut init_app_builder: ParseBevyModel, expr: Box<Expr>) -> ParseBevyM
 
 #[cfg(test)]
 mod tests {
+    use codegen::Scope;
+
+    use crate::{generate::GenerationType, model::BevyModel};
+
     use super::*;
 
     #[test]
@@ -220,6 +337,147 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_building_a_bevy_app_finds_events() {
+        let syntax = syn::parse_file(
+            r#"fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_event::<CollisionEvent>()
+        .add_event::<ScoreEvent>()
+        .run();
+    }"#,
+        )
+        .expect("Unable to parse file");
+        let res = parse_file(syntax).expect("Unable to find app");
+        assert_eq!(
+            res.events,
+            vec!["CollisionEvent".to_string(), "ScoreEvent".to_string()]
+        );
+        assert!(res
+            .app_builder
+            .contains(&("add_event::<CollisionEvent>".to_string(), "".to_string())));
+
+        let bevy_model: BevyModel = res.into();
+        let main = bevy_model
+            .generate_code(Scope::new(), GenerationType::Main)
+            .to_string();
+        assert!(main.contains(".add_event::<CollisionEvent>().add_event::<ScoreEvent>()"));
+    }
+
+    #[test]
+    fn parse_registered_types_marks_components_reflected() {
+        let bevy_file = r#"
+        #[derive(Component)]
+        pub struct Player;
+
+        #[derive(Component)]
+        pub struct Bullet;
+
+        fn main() {
+            App::new()
+                .add_plugins(DefaultPlugins)
+                .register_type::<Player>()
+                .run();
+        }
+        "#;
+
+        let res = parse_file(syn::parse_file(bevy_file).expect("Unable to parse file"))
+            .expect("Unable to find app");
+        assert_eq!(res.registered_types, vec!["Player".to_string()]);
+
+        let bevy_model: BevyModel = res.into();
+        let reflected = bevy_model
+            .components
+            .iter()
+            .map(|c| (c.name.as_str(), c.is_reflected))
+            .collect::<Vec<_>>();
+        assert_eq!(reflected, vec![("Player", true), ("Bullet", false)]);
+    }
+
+    #[test]
+    fn parse_components_of_all_shapes() {
+        let bevy_file = r#"
+        #[derive(Component)]
+        pub struct Player {
+            pub velocity: Vec3,
+            pub shooting_timer: Option<f32>,
+        }
+
+        #[derive(Component, Reflect, Default)]
+        #[reflect(Component)]
+        pub struct Health(pub f32, pub (f32, f32));
+
+        #[derive(Component)]
+        pub struct Bullet;
+
+        #[derive(Component, Debug)]
+        pub enum Team {
+            Red,
+            Blue,
+            Neutral(u8),
+        }
+
+        pub struct NotAComponent;
+        "#;
+
+        let res = parse_file(syn::parse_file(bevy_file).expect("Unable to parse file"))
+            .expect("Unable to find components");
+        assert_eq!(
+            res.components,
+            vec![
+                Component {
+                    name: "Player".to_string(),
+                    content: vec![
+                        ComponentField {
+                            name: "velocity".to_string(),
+                            ty: "Vec3".to_string(),
+                            default: None
+                        },
+                        ComponentField {
+                            name: "shooting_timer".to_string(),
+                            ty: "Option<f32>".to_string(),
+                            default: None
+                        }
+                    ],
+                    is_reflected: false,
+                    ..Default::default()
+                },
+                Component {
+                    name: "Health".to_string(),
+                    shape: ComponentShape::Tuple(vec!["f32".to_string(), "(f32, f32)".to_string()]),
+                    ..Default::default()
+                },
+                Component {
+                    name: "Bullet".to_string(),
+                    shape: ComponentShape::Unit,
+                    is_reflected: false,
+                    ..Default::default()
+                },
+                Component {
+                    name: "Team".to_string(),
+                    shape: ComponentShape::Enum(vec![
+                        ComponentVariant {
+                            name: "Red".to_string(),
+                            fields: vec![]
+                        },
+                        ComponentVariant {
+                            name: "Blue".to_string(),
+                            fields: vec![]
+                        },
+                        ComponentVariant {
+                            name: "Neutral".to_string(),
+                            fields: vec!["u8".to_string()]
+                        },
+                    ]),
+                    derives: vec!["Debug".to_string()],
+                    is_reflected: false,
+                    ..Default::default()
+                },
+            ]
+        );
+    }
+
     #[test]
     fn parse_use() {
         let bevy_file = r#"
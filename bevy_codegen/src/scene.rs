@@ -0,0 +1,266 @@
+use crate::model::{
+    BevyModel, Component, ComponentInstance, ComponentShape, EntityPrefab, System, SystemParam,
+};
+
+pub const SPAWN_SYSTEM_NAME: &str = "spawn_entities";
+
+impl BevyModel {
+    pub fn spawn_system(&self) -> Option<System> {
+        if self.entities.is_empty() {
+            return None;
+        }
+        let content = self
+            .entities
+            .iter()
+            .map(|e| format!("commands{};", self.spawn_code(e)))
+            .collect::<Vec<String>>()
+            .join("\n");
+        Some(System {
+            name: SPAWN_SYSTEM_NAME.to_string(),
+            param: vec![SystemParam::Commands {
+                name: "commands".to_string(),
+            }],
+            content,
+            attributes: vec![],
+            ..Default::default()
+        })
+    }
+
+    pub fn scene_ron(&self) -> Option<String> {
+        if self.entities.is_empty() {
+            return None;
+        }
+        let mut entities = vec![];
+        for entity in &self.entities {
+            self.scene_entity(entity, None, &mut entities);
+        }
+        Some(format!(
+            "(\n  resources: {{}},\n  entities: {{\n{}\n  }},\n)\n",
+            entities.join("\n")
+        ))
+    }
+
+    fn spawn_code(&self, entity: &EntityPrefab) -> String {
+        let mut bundle = vec![format!("Name::new(\"{}\")", entity.name)];
+        bundle.extend(entity.components.iter().map(|c| self.instance_code(c)));
+        let mut code = format!(".spawn(({},))", bundle.join(", "));
+        if !entity.children.is_empty() {
+            let children = entity
+                .children
+                .iter()
+                .map(|c| format!("parent{};", self.spawn_code(c)))
+                .collect::<Vec<String>>()
+                .join("\n");
+            code.push_str(&format!(".with_children(|parent| {{\n{children}\n}})"));
+        }
+        code
+    }
+
+    fn instance_code(&self, instance: &ComponentInstance) -> String {
+        if let Some(value) = &instance.value {
+            return value.clone();
+        }
+        let component = self.find_component(&instance.name);
+        let values = instance.fields.iter().map(|(_, v)| v.clone());
+        let fields = instance.fields.iter().map(|(n, v)| format!("{n}: {v}"));
+        match component.map(|c| (&c.shape, c.content.len())) {
+            Some((ComponentShape::Unit, _)) | Some((ComponentShape::Struct, 0)) => {
+                instance.name.clone()
+            }
+            Some((ComponentShape::Tuple(_), _)) => {
+                format!(
+                    "{}({})",
+                    instance.name,
+                    values.collect::<Vec<_>>().join(", ")
+                )
+            }
+            _ if instance.fields.is_empty() => format!("{}::default()", instance.name),
+            Some((ComponentShape::Struct, len)) if len == instance.fields.len() => {
+                format!(
+                    "{} {{ {} }}",
+                    instance.name,
+                    fields.collect::<Vec<_>>().join(", ")
+                )
+            }
+            _ => format!(
+                "{} {{ {}, ..default() }}",
+                instance.name,
+                fields.collect::<Vec<_>>().join(", ")
+            ),
+        }
+    }
+
+    // Entities are numbered depth-first, only reflected model components can be written
+    fn scene_entity(
+        &self,
+        entity: &EntityPrefab,
+        parent: Option<usize>,
+        entities: &mut Vec<String>,
+    ) -> usize {
+        let id = entities.len();
+        entities.push(String::new());
+
+        let mut components = vec![];
+        for instance in entity.components.iter().filter(|i| i.value.is_none()) {
+            let component = match self.find_component(&instance.name) {
+                Some(c) if c.is_reflected => c,
+                _ => continue,
+            };
+            let value = match component.shape {
+                ComponentShape::Enum(_) => continue,
+                ComponentShape::Tuple(_) => {
+                    let values = instance.fields.iter().map(|(_, v)| v.clone());
+                    format!("({})", values.collect::<Vec<_>>().join(", "))
+                }
+                _ => {
+                    let fields = instance.fields.iter().map(|(n, v)| format!("{n}: {v}"));
+                    format!("({})", fields.collect::<Vec<_>>().join(", "))
+                }
+            };
+            components.push(format!("\"components::{}\": {value},", instance.name));
+        }
+
+        let children = entity
+            .children
+            .iter()
+            .map(|c| self.scene_entity(c, Some(id), entities).to_string())
+            .collect::<Vec<String>>();
+        if !children.is_empty() {
+            components.push(format!(
+                "\"bevy_hierarchy::components::children::Children\": ([{}]),",
+                children.join(", ")
+            ));
+        }
+        if let Some(parent) = parent {
+            components.push(format!(
+                "\"bevy_hierarchy::components::parent::Parent\": ({parent}),"
+            ));
+        }
+
+        let components = components
+            .iter()
+            .map(|c| format!("        {c}"))
+            .collect::<Vec<String>>()
+            .join("\n");
+        entities[id] =
+            format!("    {id}: (\n      components: {{\n{components}\n      }},\n    ),");
+        id
+    }
+
+    fn find_component(&self, name: &str) -> Option<&Component> {
+        self.components.iter().find(|c| c.name.eq(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::ComponentField;
+
+    use super::*;
+
+    fn level() -> BevyModel {
+        BevyModel {
+            components: vec![
+                Component {
+                    name: "Player".to_string(),
+                    content: vec![
+                        ComponentField {
+                            name: "speed".to_string(),
+                            ty: "f32".to_string(),
+                            default: None,
+                        },
+                        ComponentField {
+                            name: "lives".to_string(),
+                            ty: "u8".to_string(),
+                            default: None,
+                        },
+                    ],
+                    ..Default::default()
+                },
+                Component {
+                    name: "Health".to_string(),
+                    shape: ComponentShape::Tuple(vec!["f32".to_string()]),
+                    ..Default::default()
+                },
+                Component {
+                    name: "Gun".to_string(),
+                    ..Default::default()
+                },
+            ],
+            entities: vec![EntityPrefab {
+                name: "Player".to_string(),
+                components: vec![
+                    ComponentInstance {
+                        name: "Player".to_string(),
+                        fields: vec![("speed".to_string(), "2.5".to_string())],
+                        ..Default::default()
+                    },
+                    ComponentInstance {
+                        name: "Health".to_string(),
+                        fields: vec![("0".to_string(), "100.0".to_string())],
+                        ..Default::default()
+                    },
+                    ComponentInstance {
+                        name: "Transform".to_string(),
+                        value: Some("Transform::from_xyz(0.0, 1.0, 0.0)".to_string()),
+                        ..Default::default()
+                    },
+                ],
+                children: vec![EntityPrefab {
+                    name: "Gun".to_string(),
+                    components: vec![ComponentInstance {
+                        name: "Gun".to_string(),
+                        ..Default::default()
+                    }],
+                    children: vec![],
+                }],
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn spawn_system_spawns_hierarchy() {
+        let system = level().spawn_system().expect("Entities should spawn");
+        assert_eq!(SPAWN_SYSTEM_NAME, system.name);
+        assert_eq!(
+            system.content,
+            r#"commands.spawn((Name::new("Player"), Player { speed: 2.5, ..default() }, Health(100.0), Transform::from_xyz(0.0, 1.0, 0.0),)).with_children(|parent| {
+parent.spawn((Name::new("Gun"), Gun,));
+});"#
+        );
+    }
+
+    #[test]
+    fn scene_contains_reflected_components_and_hierarchy() {
+        assert_eq!(
+            level().scene_ron().expect("Entities should be written"),
+            r#"(
+  resources: {},
+  entities: {
+    0: (
+      components: {
+        "components::Player": (speed: 2.5),
+        "components::Health": (100.0),
+        "bevy_hierarchy::components::children::Children": ([1]),
+      },
+    ),
+    1: (
+      components: {
+        "components::Gun": (),
+        "bevy_hierarchy::components::parent::Parent": (0),
+      },
+    ),
+  },
+)
+"#
+        );
+    }
+
+    #[test]
+    fn no_entities_generates_nothing() {
+        let bm = BevyModel::default();
+        assert_eq!(None, bm.spawn_system());
+        assert_eq!(None, bm.scene_ron());
+    }
+}
@@ -0,0 +1,112 @@
+use jsonschema::JSONSchema;
+use schemars::{schema::RootSchema, schema_for};
+use serde_json::Value;
+
+use crate::{
+    model::{ConfirmPo2Version, Po2Version},
+    validate::{Diagnostic, Severity},
+};
+
+// Schema of the current `.po2.json` format, published as `schema/po2-<version>.schema.json`
+pub fn po2_schema() -> RootSchema {
+    let mut schema = schema_for!(ConfirmPo2Version);
+    let metadata = schema.schema.metadata();
+    metadata.title = Some(format!("po2 {}", Po2Version::default()));
+    metadata.description = Some("Potoo project file (.po2.json)".to_string());
+    schema
+}
+
+pub fn po2_schema_json() -> String {
+    serde_json::to_string_pretty(&po2_schema()).unwrap() + "\n"
+}
+
+// Checks a `.po2.json` file against the schema before it is loaded
+pub fn validate_po2_json(json: &str) -> Vec<Diagnostic> {
+    let error = |path: &str, message: String| Diagnostic {
+        severity: Severity::Error,
+        path: path.to_string(),
+        message,
+    };
+
+    let instance = match serde_json::from_str::<Value>(json) {
+        Ok(instance) => instance,
+        Err(e) => return vec![error("", format!("not valid JSON: {e}"))],
+    };
+    let current = serde_json::to_value(Po2Version::default()).unwrap();
+    match instance.get("po2_version") {
+        Some(version) if *version == current => (),
+        Some(version) => {
+            return vec![error(
+                "/po2_version",
+                format!("written as {version}, the schema covers {current}"),
+            )]
+        }
+        None => return vec![error("", "missing `po2_version`".to_string())],
+    }
+
+    let schema = serde_json::to_value(po2_schema()).unwrap();
+    let compiled = JSONSchema::compile(&schema).expect("po2 schema should compile");
+    let result = compiled.validate(&instance);
+    match result {
+        Ok(()) => vec![],
+        Err(errors) => errors
+            .map(|e| error(&e.instance_path.to_string(), e.to_string()))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        bevy_model_template::default_game_template,
+        model::{BevyModel, ReadPo2Version},
+    };
+
+    use super::*;
+
+    fn po2_file(model: BevyModel) -> String {
+        serde_json::to_string(&ReadPo2Version {
+            po2_version: Po2Version::default(),
+            model,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn published_schema_is_up_to_date() {
+        // Regenerate with `cargo run -- schema > bevy_codegen/schema/po2-<version>.schema.json`
+        assert_eq!(
+            include_str!("../schema/po2-0.0.2.schema.json"),
+            po2_schema_json()
+        );
+    }
+
+    #[test]
+    fn written_files_match_schema() {
+        let json = po2_file(default_game_template());
+        assert_eq!(Vec::<Diagnostic>::new(), validate_po2_json(&json));
+    }
+
+    #[test]
+    fn hand_edits_are_checked() {
+        let mut json = serde_json::from_str::<Value>(&po2_file(BevyModel::default())).unwrap();
+        json["model"]["components"] = serde_json::json!([{ "name": 1 }]);
+        let diagnostics = validate_po2_json(&json.to_string());
+        assert!(!diagnostics.is_empty());
+        assert!(diagnostics
+            .iter()
+            .all(|d| d.severity == Severity::Error && d.path.starts_with("/model/components/0")));
+    }
+
+    #[test]
+    fn other_versions_are_reported() {
+        let json = po2_file(BevyModel::default()).replace("\"V0_0_2\"", "\"V0_0_1\"");
+        assert_eq!(
+            vec!["error: /po2_version: written as \"V0_0_1\", the schema covers \"V0_0_2\""],
+            validate_po2_json(&json)
+                .iter()
+                .map(|d| d.to_string())
+                .collect::<Vec<String>>()
+        );
+    }
+}
@@ -1,6 +1,4 @@
-use crate::model::{BevyModel, DependencyType, Feature};
-
-const BEVY_VERSION: &str = "0.10";
+use crate::model::{AssetFormat, BevyModel, DependencyType, Feature};
 
 pub fn feature_write(features: &Vec<Feature>) -> String {
     let mut features_str = "".to_owned();
@@ -20,7 +18,16 @@ pub fn feature_write(features: &Vec<Feature>) -> String {
     features_str
 }
 
-pub fn default_cargo_components_template() -> String {
+pub fn default_cargo_components_template(model: &BevyModel) -> String {
+    let bevy_version = model.bevy_settings.bevy_version.to_version();
+    let dynamic = model
+        .bevy_settings
+        .bevy_version
+        .dynamic_feature()
+        .to_feature();
+
+    let asset_deps = custom_asset_dependencies(model);
+
     format!(
         r#"[package]
 name = "components"
@@ -30,16 +37,46 @@ edition = "2021"
 # See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
 
 [dependencies]
-bevy = "{BEVY_VERSION}"
-
+bevy = "{bevy_version}"
+{asset_deps}
 [features]
 default = []
-dynamic = ["bevy/dynamic"]
+dynamic = ["bevy/{dynamic}"]
 "#
     )
 }
 
-pub fn default_cargo_systems_template() -> String {
+// Custom asset loaders deserialize with serde
+fn custom_asset_dependencies(model: &BevyModel) -> String {
+    let mut asset_deps = "".to_owned();
+    if !model.custom_assets.is_empty() {
+        asset_deps.push_str("serde = { version = \"1.0\", features = [\"derive\"] }\n");
+    }
+    if model
+        .custom_assets
+        .iter()
+        .any(|a| a.format == AssetFormat::Ron)
+    {
+        asset_deps.push_str("ron = \"0.8\"\n");
+    }
+    if model
+        .custom_assets
+        .iter()
+        .any(|a| a.format == AssetFormat::Json)
+    {
+        asset_deps.push_str("serde_json = \"1.0\"\n");
+    }
+
+    asset_deps
+}
+
+pub fn default_cargo_systems_template(model: &BevyModel) -> String {
+    let bevy_version = model.bevy_settings.bevy_version.to_version();
+    let dynamic = model
+        .bevy_settings
+        .bevy_version
+        .dynamic_feature()
+        .to_feature();
     format!(
         r#"[package]
 name = "systems"
@@ -50,63 +87,43 @@ edition = "2021"
 crate-type = ["rlib", "dylib"]
 
 [dependencies]
-bevy = "{BEVY_VERSION}"
+bevy = "{bevy_version}"
 components = {{ path = "../components" }}
 log = "0.4.17"
 rand = "0.8.5"
 
 [features]
 default = []
-dynamic = ["bevy/dynamic", "components/dynamic"]
+dynamic = ["bevy/{dynamic}", "components/dynamic"]
 "#
     )
 }
 
 pub fn default_cargo_src_template(model: &BevyModel) -> String {
     let bevy_folder = model.meta.name.clone();
+    let bevy_version = model.bevy_settings.bevy_version.to_version();
+    let dynamic = model
+        .bevy_settings
+        .bevy_version
+        .dynamic_feature()
+        .to_feature();
 
     let features = feature_write(&model.bevy_settings.features);
     let dev_features = feature_write(&model.bevy_settings.dev_features);
 
-    let crate_deps = model
-        .plugins
-        .iter()
-        .map(|d| {
-            let mut s = "".to_owned();
-            for b in d.dependencies.iter() {
-                let k = match &b.dependency_type {
-                    DependencyType::Crate(version) => Some(format!("{0} = \"{version}\"", b.name)),
-                    DependencyType::Git(git, Some(branch), Some(rev)) => Some(format!(
-                        "{0} = {{ git = \"{git}\", branch = \"{branch}\", rev = \"{rev}\"",
-                        b.name
-                    )),
-                    DependencyType::Git(git, Some(branch), None) => Some(format!(
-                        "{0} = {{ git = \"{git}\", branch = \"{branch}\"",
-                        b.name
-                    )),
-                    DependencyType::Git(git, None, Some(rev)) => {
-                        Some(format!("{0} = {{ git = \"{git}\", rev = \"{rev}\"", b.name))
-                    }
-                    DependencyType::Git(git, None, None) => {
-                        Some(format!("{0} = {{ git = \"{git}\"", b.name))
-                    }
-                    DependencyType::Path(path) => {
-                        Some(format!("{0} = {{ path = \"{path}\"", b.name))
-                    }
-                    DependencyType::Internal => None,
-                };
-                if let Some(dep) = k {
-                    s.push_str(&dep);
-                    if !&b.features.is_empty() {
-                        s.push_str(format!(", features = [{}]", b.features.join(",")).as_str());
-                    }
-                    s.push_str(" }");
-                }
-            }
-            s.to_string()
-        })
+    let crate_deps = plugin_dependencies(model);
+    let plugin_crate_deps = plugin_crate_dependencies(model, "");
+    let members = ["systems", "components"]
+        .into_iter()
+        .map(|m| format!("\"{m}\""))
+        .chain(
+            model
+                .all_plugin_crates()
+                .iter()
+                .map(|p| format!("\"{}\"", p.meta.name)),
+        )
         .collect::<Vec<String>>()
-        .join("\n");
+        .join(", ");
 
     let buf = format!(
         r#"[package]
@@ -116,7 +133,7 @@ edition = "2021"
 
 [workspace]
 resolver = "2"
-members = ["systems", "components"]
+members = [{members}]
 
 # Enable only a small amount of optimization in debug mode
 [profile.dev]
@@ -138,23 +155,115 @@ reload = [
   # Make sure that the types don't change:
   "components/dynamic",
   # This is important on windows for avoiding file locking issues:
-  "bevy/dynamic",
+  "bevy/{dynamic}",
 ]
 
 [dependencies]
 components = {{ path = "components" }}
 systems = {{ path = "systems" }}
 hot-lib-reloader = {{ version = "0.6.5", optional = true }}
-{crate_deps}
+{plugin_crate_deps}{crate_deps}
 
 [dependencies.bevy]
-version = "{BEVY_VERSION}"
+version = "{bevy_version}"
 {features}
 
 [dev-dependencies.bevy]
-version = "{BEVY_VERSION}"
+version = "{bevy_version}"
 {dev_features}
 "#
     );
     buf
 }
+
+fn plugin_dependencies(model: &BevyModel) -> String {
+    model
+        .plugins
+        .iter()
+        .map(|d| {
+            let mut s = "".to_owned();
+            for b in d.dependencies.iter() {
+                let k = match &b.dependency_type {
+                    DependencyType::Crate(version) => Some(format!("{0} = \"{version}\"", b.name)),
+                    DependencyType::Git(git, Some(branch), Some(rev)) => Some(format!(
+                        "{0} = {{ git = \"{git}\", branch = \"{branch}\", rev = \"{rev}\"",
+                        b.name
+                    )),
+                    DependencyType::Git(git, Some(branch), None) => Some(format!(
+                        "{0} = {{ git = \"{git}\", branch = \"{branch}\"",
+                        b.name
+                    )),
+                    DependencyType::Git(git, None, Some(rev)) => {
+                        Some(format!("{0} = {{ git = \"{git}\", rev = \"{rev}\"", b.name))
+                    }
+                    DependencyType::Git(git, None, None) => {
+                        Some(format!("{0} = {{ git = \"{git}\"", b.name))
+                    }
+                    DependencyType::Path(path) => {
+                        Some(format!("{0} = {{ path = \"{path}\"", b.name))
+                    }
+                    DependencyType::Internal => None,
+                };
+                if let Some(dep) = k {
+                    s.push_str(&dep);
+                    if !&b.features.is_empty() {
+                        s.push_str(format!(", features = [{}]", b.features.join(",")).as_str());
+                    }
+                    s.push_str(" }");
+                }
+            }
+            s.to_string()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// Plugin crates are siblings in the root folder
+fn plugin_crate_dependencies(model: &BevyModel, prefix: &str) -> String {
+    model
+        .plugin_crates
+        .iter()
+        .map(|p| format!("{0} = {{ path = \"{prefix}{0}\" }}\n", p.meta.name))
+        .collect::<String>()
+}
+
+pub fn default_cargo_plugin_crate_template(model: &BevyModel) -> String {
+    let name = model.meta.name.clone();
+    let bevy_version = model.bevy_settings.bevy_version.to_version();
+    let crate_deps = plugin_dependencies(model);
+    let plugin_crate_deps = plugin_crate_dependencies(model, "../");
+    let asset_deps = custom_asset_dependencies(model);
+
+    format!(
+        r#"[package]
+name = "{name}"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bevy = "{bevy_version}"
+{asset_deps}{plugin_crate_deps}{crate_deps}
+"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::CustomAsset;
+
+    use super::*;
+
+    #[test]
+    fn plugin_crate_depends_on_serde_for_custom_assets() {
+        let mut model = BevyModel::default();
+        assert!(!default_cargo_plugin_crate_template(&model).contains("serde"));
+
+        model.custom_assets.push(CustomAsset {
+            format: AssetFormat::Ron,
+            ..Default::default()
+        });
+        let toml = default_cargo_plugin_crate_template(&model);
+        assert!(toml.contains("serde = { version = \"1.0\", features = [\"derive\"] }\n"));
+        assert!(toml.contains("ron = \"0.8\"\n"));
+    }
+}
@@ -0,0 +1,445 @@
+use std::fmt::Display;
+
+use proc_macro2::{LineColumn, Span};
+use syn::{
+    spanned::Spanned,
+    visit::{self, Visit},
+    Block, Expr, ExprMethodCall, Path,
+};
+
+use crate::model::{BevyModel, BevyVersion, Custom, Feature};
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct UpgradeReport {
+    pub from: BevyVersion,
+    pub to: BevyVersion,
+    pub migrated: Vec<String>,
+    // Everything that still has to be changed by hand
+    pub manual: Vec<String>,
+}
+
+impl Display for UpgradeReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let _ = writeln!(
+            f,
+            "Upgrade from Bevy {} to {}:",
+            self.from.to_version(),
+            self.to.to_version()
+        );
+
+        let _ = writeln!(f, "   Migrated:");
+        self.migrated.iter().for_each(|d| {
+            let _ = writeln!(f, "       {d}");
+        });
+
+        let _ = writeln!(f);
+
+        let _ = writeln!(f, "   Manual:");
+        self.manual.iter().for_each(|d| {
+            let _ = writeln!(f, "       {d}");
+        });
+
+        Ok(())
+    }
+}
+
+impl BevyModel {
+    // Rewrites the model for the next Bevy version, None if it already targets the latest one
+    pub fn upgrade(&mut self) -> Option<UpgradeReport> {
+        let from = self.bevy_settings.bevy_version;
+        let to = from.next()?;
+        let mut report = UpgradeReport {
+            from,
+            to,
+            migrated: vec![],
+            manual: vec![],
+        };
+
+        let mut rewrite = match to {
+            BevyVersion::V0_10 => return None,
+            BevyVersion::V0_11 => Rewrite0_11::default(),
+        };
+        for system in self
+            .startup_systems
+            .iter_mut()
+            .chain(self.systems.iter_mut())
+        {
+            upgrade_code(
+                &system.name,
+                &mut system.content,
+                false,
+                &mut rewrite,
+                &mut report,
+            );
+        }
+        for custom in self.custom.iter_mut() {
+            let (Custom::Main(code) | Custom::Component(code) | Custom::System(code)) = custom;
+            upgrade_code(
+                &code.name,
+                &mut code.content,
+                true,
+                &mut rewrite,
+                &mut report,
+            );
+        }
+        for plugin in &self.plugins {
+            report_manual(&plugin.name, &plugin.app_config(), &mut report);
+        }
+
+        upgrade_features(&mut self.bevy_settings.features, &mut report);
+        upgrade_features(&mut self.bevy_settings.dev_features, &mut report);
+        self.bevy_settings.bevy_version = to;
+
+        for child in self
+            .plugin_crates
+            .iter_mut()
+            .chain(self.examples.iter_mut())
+            .filter(|c| c.bevy_settings.bevy_version == from)
+        {
+            if let Some(child_report) = child.upgrade() {
+                report.migrated.extend(child_report.migrated);
+                report.manual.extend(child_report.manual);
+            }
+        }
+
+        Some(report)
+    }
+}
+
+// Systems hold a function body, custom code a whole file. Only the rewritten tokens are
+// replaced, comments and layout of the rest stay untouched
+fn upgrade_code(
+    source: &str,
+    code: &mut String,
+    is_file: bool,
+    rewrite: &mut Rewrite0_11,
+    report: &mut UpgradeReport,
+) {
+    rewrite.edits.clear();
+    rewrite.renamed.clear();
+    let text = match is_file {
+        true => code.clone(),
+        // On separate lines, a trailing `//` comment would swallow the brace otherwise
+        false => format!("{{\n{code}\n}}"),
+    };
+    let parsed = if is_file {
+        syn::parse_file(&text).map(|file| rewrite.visit_file(&file))
+    } else {
+        syn::parse_str::<Block>(&text).map(|block| rewrite.visit_block(&block))
+    };
+    if parsed.is_err() {
+        report
+            .manual
+            .push(format!("{source}: could not be parsed, check it by hand"));
+        return;
+    }
+
+    if !rewrite.edits.is_empty() {
+        let upgraded = apply_edits(&text, &mut rewrite.edits);
+        *code = match is_file {
+            true => upgraded,
+            // Strips the `{..}` wrapper again
+            false => upgraded[2..upgraded.len() - 2].to_string(),
+        };
+        for renamed in rewrite.renamed.drain(..) {
+            report.migrated.push(format!("{source}: {renamed}"));
+        }
+    }
+    report_manual(source, code, report);
+}
+
+// Replaces the text between the `start` and `end` of each edit, back to front so earlier
+// offsets stay valid
+fn apply_edits(text: &str, edits: &mut [Edit]) -> String {
+    let line_starts = std::iter::once(0)
+        .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+        .collect::<Vec<usize>>();
+    // Columns count chars, not bytes
+    let offset = |at: LineColumn| {
+        let line_start = line_starts[at.line - 1];
+        text[line_start..]
+            .char_indices()
+            .nth(at.column)
+            .map_or(text.len(), |(i, _)| line_start + i)
+    };
+
+    let mut upgraded = text.to_string();
+    // An insert sorts before a replace at the same start, so the range is replaced first and
+    // the insert lands in front of the new text instead of inside the old one
+    edits.sort_by_key(|e| (e.start.line, e.start.column, e.start != e.end));
+    for edit in edits.iter().rev() {
+        upgraded.replace_range(offset(edit.start)..offset(edit.end), &edit.text);
+    }
+    upgraded
+}
+
+// APIs removed in Bevy 0.11 without a direct replacement
+const MANUAL_0_11: [&str; 6] = [
+    "in_base_set",
+    "in_schedule",
+    "CoreSet",
+    "CoreSchedule",
+    "OnUpdate",
+    "watch_for_changes",
+];
+
+fn report_manual(source: &str, code: &str, report: &mut UpgradeReport) {
+    for api in MANUAL_0_11 {
+        if code.contains(api) {
+            report
+                .manual
+                .push(format!("{source}: `{api}` has no automatic replacement"));
+        }
+    }
+}
+
+fn upgrade_features(features: &mut Vec<Feature>, report: &mut UpgradeReport) {
+    for feature in features.iter_mut() {
+        if *feature == Feature::Dynamic {
+            *feature = Feature::DynamicLinking;
+            report
+                .migrated
+                .push("feature `dynamic` -> `dynamic_linking`".to_string());
+        }
+    }
+    if features.contains(&Feature::SubpixelGlyphAtlas) {
+        features.retain(|f| *f != Feature::SubpixelGlyphAtlas);
+        report
+            .migrated
+            .push("feature `subpixel_glyph_atlas` removed".to_string());
+    }
+}
+
+struct Edit {
+    start: LineColumn,
+    end: LineColumn,
+    text: String,
+}
+
+#[derive(Default)]
+struct Rewrite0_11 {
+    edits: Vec<Edit>,
+    renamed: Vec<String>,
+}
+
+impl Rewrite0_11 {
+    fn replace(&mut self, span: Span, text: &str) {
+        self.edits.push(Edit {
+            start: span.start(),
+            end: span.end(),
+            text: text.to_string(),
+        });
+    }
+
+    fn insert_before(&mut self, span: Span, text: &str) {
+        self.edits.push(Edit {
+            start: span.start(),
+            end: span.start(),
+            text: text.to_string(),
+        });
+    }
+}
+
+impl<'ast> Visit<'ast> for Rewrite0_11 {
+    fn visit_expr_method_call(&mut self, call: &'ast ExprMethodCall) {
+        visit::visit_expr_method_call(self, call);
+        let method = call.method.to_string();
+        let span = call.method.span();
+        let schedule = match method.as_str() {
+            "add_plugin" => {
+                self.replace(span, "add_plugins");
+                self.renamed
+                    .push("`add_plugin` -> `add_plugins`".to_string());
+                None
+            }
+            "add_startup_system" | "add_startup_systems" => Some("Startup"),
+            "add_system" => Some("Update"),
+            "add_systems" | "configure_set" | "configure_sets" if call.args.len() == 1 => {
+                Some("Update")
+            }
+            "in_set" => {
+                if let Some(Expr::Call(inner)) = call.args.first() {
+                    if let Expr::Path(p) = inner.func.as_ref() {
+                        if p.path.is_ident("OnUpdate") {
+                            self.replace(span, "run_if");
+                            self.replace(p.path.segments[0].ident.span(), "in_state");
+                            self.renamed.push(
+                                "`in_set(OnUpdate(..))` -> `run_if(in_state(..))`".to_string(),
+                            );
+                        }
+                    }
+                }
+                None
+            }
+            _ => None,
+        };
+        if let Some(schedule) = schedule {
+            let renamed = match method.as_str() {
+                "configure_set" | "configure_sets" => method.clone(),
+                _ => "add_systems".to_string(),
+            };
+            if renamed != method {
+                self.replace(span, &renamed);
+            }
+            // In front of the first argument so it keeps its line
+            match call.args.first() {
+                Some(arg) => self.insert_before(arg.span(), &format!("{schedule}, ")),
+                None => self.insert_before(call.paren_token.span.close(), schedule),
+            }
+            self.renamed
+                .push(format!("`{method}(..)` -> `{renamed}({schedule}, ..)`"));
+        }
+    }
+
+    fn visit_path(&mut self, path: &'ast Path) {
+        visit::visit_path(self, path);
+        for segment in path.segments.iter() {
+            if segment.ident == "apply_system_buffers" {
+                self.replace(segment.ident.span(), "apply_deferred");
+                self.renamed
+                    .push("`apply_system_buffers` -> `apply_deferred`".to_string());
+            }
+        }
+        let len = path.segments.len();
+        if len >= 2
+            && path.segments[len - 2].ident == "ScalingMode"
+            && path.segments[len - 1].ident == "Auto"
+        {
+            self.replace(path.segments[len - 1].ident.span(), "AutoMin");
+            self.renamed
+                .push("`ScalingMode::Auto` -> `ScalingMode::AutoMin`".to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::{CustomCode, Settings, System};
+
+    use super::*;
+
+    fn without_whitespace(code: &str) -> String {
+        code.replace(char::is_whitespace, "")
+    }
+
+    #[test]
+    fn upgrade_rewrites_app_builder_calls() {
+        let mut bm = BevyModel {
+            custom: vec![Custom::Main(CustomCode {
+                id: Default::default(),
+                name: "app.rs".to_string(),
+                content: "fn build(app: &mut App) { app.add_plugin(FooPlugin).add_startup_system(setup).add_system(movement.in_set(OnUpdate(AppState::InGame))); }".to_string(),
+            })],
+            ..Default::default()
+        };
+        let report = bm.upgrade().expect("0.10 should upgrade");
+        let Custom::Main(code) = &bm.custom[0] else {
+            panic!("Custom code should stay main code");
+        };
+        assert_eq!(
+            without_whitespace(&code.content),
+            "fnbuild(app:&mutApp){app.add_plugins(FooPlugin).add_systems(Startup,setup).add_systems(Update,movement.run_if(in_state(AppState::InGame)));}"
+        );
+        assert_eq!(4, report.migrated.len());
+        assert!(report.manual.is_empty());
+        assert_eq!(BevyVersion::V0_11, bm.bevy_settings.bevy_version);
+    }
+
+    #[test]
+    fn upgrade_rewrites_system_content_and_reports_manual_changes() {
+        let mut bm = BevyModel {
+            systems: vec![
+                System {
+                    name: "camera".to_string(),
+                    content: "let mode = ScalingMode::Auto { min_width: 1.0, min_height: 1.0 };"
+                        .to_string(),
+                    ..Default::default()
+                },
+                System {
+                    name: "schedule".to_string(),
+                    content: "app.add_system(physics.in_schedule(CoreSchedule::FixedUpdate));"
+                        .to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let report = bm.upgrade().expect("0.10 should upgrade");
+        assert_eq!(
+            bm.systems[0].content,
+            "let mode = ScalingMode::AutoMin { min_width: 1.0, min_height: 1.0 };"
+        );
+        assert!(report
+            .migrated
+            .contains(&"camera: `ScalingMode::Auto` -> `ScalingMode::AutoMin`".to_string()));
+        assert!(report
+            .manual
+            .contains(&"schedule: `in_schedule` has no automatic replacement".to_string()));
+        assert!(report
+            .manual
+            .contains(&"schedule: `CoreSchedule` has no automatic replacement".to_string()));
+    }
+
+    #[test]
+    fn upgrade_keeps_comments_and_layout() {
+        let content = "// Menu only\napp.add_system(\n    menu.in_set(OnUpdate(AppState::Menu)), // keep\n);\nlet ümlaut = ScalingMode::Auto { min_width: 1.0, min_height: 1.0 }; // sized";
+        let mut bm = BevyModel {
+            systems: vec![System {
+                name: "menu".to_string(),
+                content: content.to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        bm.upgrade().expect("0.10 should upgrade");
+        assert_eq!(
+            bm.systems[0].content,
+            "// Menu only\napp.add_systems(\n    Update, menu.run_if(in_state(AppState::Menu)), // keep\n);\nlet ümlaut = ScalingMode::AutoMin { min_width: 1.0, min_height: 1.0 }; // sized"
+        );
+    }
+
+    #[test]
+    fn upgrade_inserts_schedule_in_front_of_renamed_argument() {
+        let mut bm = BevyModel {
+            systems: vec![System {
+                name: "flush".to_string(),
+                content: "app.add_system(apply_system_buffers);".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        bm.upgrade().expect("0.10 should upgrade");
+        assert_eq!(
+            bm.systems[0].content,
+            "app.add_systems(Update, apply_deferred);"
+        );
+    }
+
+    #[test]
+    fn upgrade_renames_features() {
+        let mut bm = BevyModel {
+            bevy_settings: Settings {
+                features: vec![Feature::Dynamic, Feature::SubpixelGlyphAtlas, Feature::Png],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        bm.upgrade().expect("0.10 should upgrade");
+        assert_eq!(
+            vec![Feature::DynamicLinking, Feature::Png],
+            bm.bevy_settings.features
+        );
+    }
+
+    #[test]
+    fn latest_version_is_not_upgraded() {
+        let mut bm = BevyModel {
+            bevy_settings: Settings {
+                bevy_version: BevyVersion::V0_11,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(None, bm.upgrade());
+    }
+}
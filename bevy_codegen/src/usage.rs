@@ -0,0 +1,347 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::Display,
+};
+
+use syn::{
+    visit::{self, Visit},
+    Block, ExprMethodCall, GenericArgument, Path, PathArguments, Type,
+};
+
+use crate::model::{BevyModel, QueryData, QueryFilter, System, SystemParam};
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum Access {
+    Read,
+    Write,
+    Spawn,
+    Remove,
+    // Member of a bundle
+    Contain,
+}
+
+impl Display for Access {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let access = match self {
+            Access::Read => "reads",
+            Access::Write => "writes",
+            Access::Spawn => "spawns",
+            Access::Remove => "removes",
+            Access::Contain => "contains",
+        };
+        write!(f, "{access}")
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct ComponentUsage {
+    // System or bundle name
+    pub system: String,
+    pub access: Access,
+}
+
+impl Display for ComponentUsage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.system, self.access)
+    }
+}
+
+// Component name -> systems touching it
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct UsageIndex {
+    pub components: BTreeMap<String, Vec<ComponentUsage>>,
+}
+
+impl UsageIndex {
+    pub fn usages(&self, component: &str) -> &[ComponentUsage] {
+        self.components
+            .get(component)
+            .map(|u| u.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn systems(&self, component: &str, access: Access) -> Vec<&str> {
+        self.usages(component)
+            .iter()
+            .filter(|u| u.access == access)
+            .map(|u| u.system.as_str())
+            .collect()
+    }
+
+    fn insert(&mut self, component: &str, system: &str, access: Access) {
+        let usage = ComponentUsage {
+            system: system.to_string(),
+            access,
+        };
+        let usages = self.components.entry(component.to_string()).or_default();
+        if !usages.contains(&usage) {
+            usages.push(usage);
+        }
+    }
+}
+
+impl BevyModel {
+    // Query params and bundles are indexed for any component, system bodies only for model
+    // components. Generated startup systems like `spawn_entities` count as well
+    pub fn component_usage(&self) -> UsageIndex {
+        let components = self
+            .components
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect::<HashSet<&str>>();
+        let mut index = UsageIndex::default();
+        for bundle in &self.bundles {
+            for component in &bundle.components {
+                index.insert(component, &bundle.name, Access::Contain);
+            }
+        }
+        for system in self.all_startup_systems().iter().chain(self.systems.iter()) {
+            index_params(system, &mut index);
+            if let Ok(block) = syn::parse_str::<Block>(&format!("{{{}}}", system.content)) {
+                let mut visitor = BodyVisitor {
+                    components: &components,
+                    found: vec![],
+                };
+                visitor.visit_block(&block);
+                for (component, access) in visitor.found {
+                    index.insert(&component, &system.name, access);
+                }
+            }
+        }
+        index
+    }
+}
+
+fn index_params(system: &System, index: &mut UsageIndex) {
+    for param in &system.param {
+        let SystemParam::Query { data, filter, .. } = param else {
+            continue;
+        };
+        for data in data {
+            match data {
+                QueryData::Entity => (),
+                QueryData::Ref(c) => index.insert(c, &system.name, Access::Read),
+                QueryData::Mut(c) => index.insert(c, &system.name, Access::Write),
+                QueryData::Custom(ty) => {
+                    if let Ok(ty) = syn::parse_str::<Type>(ty) {
+                        for (c, access) in type_references(&ty) {
+                            index.insert(&c, &system.name, access);
+                        }
+                    }
+                }
+            }
+        }
+        // Filters only look at the component, which counts as reading it
+        for filter in filter {
+            match filter {
+                QueryFilter::With(c)
+                | QueryFilter::Without(c)
+                | QueryFilter::Added(c)
+                | QueryFilter::Changed(c) => index.insert(c, &system.name, Access::Read),
+                QueryFilter::Custom(_) => (),
+            }
+        }
+    }
+}
+
+// `&T` and `&mut T` inside tuples and wrappers like `Option<..>`
+fn type_references(ty: &Type) -> Vec<(String, Access)> {
+    match ty {
+        Type::Reference(r) => {
+            let access = match r.mutability {
+                Some(_) => Access::Write,
+                None => Access::Read,
+            };
+            match r.elem.as_ref() {
+                Type::Path(p) => last_ident(&p.path)
+                    .map(|c| vec![(c, access)])
+                    .unwrap_or_default(),
+                _ => vec![],
+            }
+        }
+        Type::Tuple(t) => t.elems.iter().flat_map(type_references).collect(),
+        Type::Paren(p) => type_references(&p.elem),
+        Type::Path(p) => p
+            .path
+            .segments
+            .iter()
+            .flat_map(|s| match &s.arguments {
+                PathArguments::AngleBracketed(args) => args
+                    .args
+                    .iter()
+                    .flat_map(|a| match a {
+                        GenericArgument::Type(t) => type_references(t),
+                        _ => vec![],
+                    })
+                    .collect(),
+                _ => vec![],
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+fn last_ident(path: &Path) -> Option<String> {
+    path.segments.last().map(|s| s.ident.to_string())
+}
+
+const SPAWN_METHODS: [&str; 4] = ["spawn", "spawn_bundle", "insert", "insert_bundle"];
+const REMOVE_METHODS: [&str; 2] = ["remove", "remove_bundle"];
+
+struct BodyVisitor<'a> {
+    components: &'a HashSet<&'a str>,
+    found: Vec<(String, Access)>,
+}
+
+impl<'ast> Visit<'ast> for BodyVisitor<'_> {
+    fn visit_expr_method_call(&mut self, call: &'ast ExprMethodCall) {
+        visit::visit_expr_method_call(self, call);
+        let method = call.method.to_string();
+        if SPAWN_METHODS.contains(&method.as_str()) {
+            let mut paths = PathCollector::default();
+            call.args.iter().for_each(|a| paths.visit_expr(a));
+            for ident in paths.idents {
+                if self.components.contains(ident.as_str()) {
+                    self.found.push((ident, Access::Spawn));
+                }
+            }
+        } else if REMOVE_METHODS.contains(&method.as_str()) {
+            let Some(turbofish) = &call.turbofish else {
+                return;
+            };
+            for arg in &turbofish.args {
+                if let GenericArgument::Type(ty) = arg {
+                    let mut paths = PathCollector::default();
+                    paths.visit_type(ty);
+                    for ident in paths.idents {
+                        if self.components.contains(ident.as_str()) {
+                            self.found.push((ident, Access::Remove));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct PathCollector {
+    idents: Vec<String>,
+}
+
+impl<'ast> Visit<'ast> for PathCollector {
+    fn visit_path(&mut self, path: &'ast Path) {
+        visit::visit_path(self, path);
+        self.idents
+            .extend(path.segments.iter().map(|s| s.ident.to_string()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::{Bundle, Component, ComponentInstance, EntityPrefab};
+
+    use super::*;
+
+    fn component(name: &str) -> Component {
+        Component {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn model() -> BevyModel {
+        BevyModel {
+            components: vec![component("Player"), component("Bullet"), component("Gun")],
+            startup_systems: vec![System {
+                name: "setup".to_string(),
+                content: "commands.spawn((Player { speed: 2.0 }, Transform::default())).with_children(|parent| { parent.spawn(Gun); });".to_string(),
+                ..Default::default()
+            }],
+            systems: vec![
+                System {
+                    name: "shoot".to_string(),
+                    param: vec![SystemParam::Query {
+                        name: "players".to_string(),
+                        data: vec![
+                            QueryData::Entity,
+                            QueryData::Custom("Option<&mut Transform>".to_string()),
+                        ],
+                        filter: vec![QueryFilter::With("Player".to_string())],
+                    }],
+                    content: "for (entity, _) in players.iter() { commands.spawn(Bullet::default()); commands.entity(entity).remove::<Gun>(); }".to_string(),
+                    ..Default::default()
+                },
+                System {
+                    name: "move_bullets".to_string(),
+                    param: vec![SystemParam::Query {
+                        name: "bullets".to_string(),
+                        data: vec![QueryData::Mut("Transform".to_string())],
+                        filter: vec![QueryFilter::With("Bullet".to_string())],
+                    }],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn usage_index_covers_params_and_bodies() {
+        let index = model().component_usage();
+        assert_eq!(
+            vec![
+                ComponentUsage {
+                    system: "setup".to_string(),
+                    access: Access::Spawn,
+                },
+                ComponentUsage {
+                    system: "shoot".to_string(),
+                    access: Access::Read,
+                },
+            ],
+            index.usages("Player")
+        );
+        assert_eq!(
+            vec!["shoot", "move_bullets"],
+            index.systems("Transform", Access::Write)
+        );
+        assert_eq!(vec!["shoot"], index.systems("Bullet", Access::Spawn));
+        assert_eq!(vec!["shoot"], index.systems("Gun", Access::Remove));
+        assert_eq!(vec!["setup"], index.systems("Gun", Access::Spawn));
+    }
+
+    #[test]
+    fn usage_index_covers_bundles_and_entities() {
+        let bm = BevyModel {
+            bundles: vec![Bundle {
+                name: "PlayerBundle".to_string(),
+                components: vec!["Player".to_string(), "SpriteBundle".to_string()],
+                ..Default::default()
+            }],
+            entities: vec![EntityPrefab {
+                name: "Gun".to_string(),
+                components: vec![ComponentInstance {
+                    name: "Gun".to_string(),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..model()
+        };
+        let index = bm.component_usage();
+        assert_eq!(
+            vec!["PlayerBundle"],
+            index.systems("Player", Access::Contain)
+        );
+        assert_eq!(
+            vec!["setup", "spawn_entities"],
+            index.systems("Gun", Access::Spawn)
+        );
+    }
+
+    #[test]
+    fn unused_component_has_no_usages() {
+        assert!(model().component_usage().usages("Enemy").is_empty());
+    }
+}
@@ -0,0 +1,339 @@
+use std::{collections::HashSet, fmt::Display};
+
+use crate::model::{BevyModel, QueryFilter, System, SystemParam};
+
+// Components shipped with Bevy that systems commonly query
+const BEVY_COMPONENTS: [&str; 24] = [
+    "Transform",
+    "GlobalTransform",
+    "Visibility",
+    "ComputedVisibility",
+    "Name",
+    "Parent",
+    "Children",
+    "Camera",
+    "Camera2d",
+    "Camera3d",
+    "OrthographicProjection",
+    "Projection",
+    "Sprite",
+    "TextureAtlasSprite",
+    "Handle",
+    "Text",
+    "Style",
+    "Node",
+    "Interaction",
+    "BackgroundColor",
+    "UiImage",
+    "Button",
+    "AnimationPlayer",
+    "Window",
+];
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    // Location in the model, e.g. `components[1]`
+    pub path: String,
+    pub message: String,
+}
+
+impl Diagnostic {
+    fn error(path: String, message: String) -> Self {
+        Self {
+            severity: Severity::Error,
+            path,
+            message,
+        }
+    }
+
+    fn warning(path: String, message: String) -> Self {
+        Self {
+            severity: Severity::Warning,
+            path,
+            message,
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "{severity}: {}: {}", self.path, self.message)
+    }
+}
+
+impl BevyModel {
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = vec![];
+
+        // Types share the components crate, systems the systems crate
+        let types = [
+            ("components", names(&self.components, |c| &c.name)),
+            ("bundles", names(&self.bundles, |b| &b.name)),
+            ("resources", names(&self.resources, |r| &r.name)),
+            ("events", names(&self.events, |e| &e.name)),
+            ("states", names(&self.states, |s| &s.name)),
+            ("system_sets", names(&self.system_sets, |s| &s.name)),
+            ("custom_assets", names(&self.custom_assets, |a| &a.name)),
+        ];
+        let systems = [
+            ("startup_systems", names(&self.startup_systems, |s| &s.name)),
+            ("systems", names(&self.systems, |s| &s.name)),
+        ];
+        for (kind, names) in types.iter().chain(&systems) {
+            for (i, name) in names.iter().enumerate() {
+                if !is_identifier(name) {
+                    diagnostics.push(Diagnostic::error(
+                        format!("{kind}[{i}]"),
+                        format!("`{name}` is not a valid Rust identifier"),
+                    ));
+                }
+            }
+        }
+        diagnostics.extend(duplicates(&types));
+        diagnostics.extend(duplicates(&systems));
+
+        for (i, plugin) in self.plugins.iter().enumerate() {
+            if syn::parse_str::<syn::Path>(&plugin.name).is_err() {
+                diagnostics.push(Diagnostic::error(
+                    format!("plugins[{i}]"),
+                    format!("`{}` is not a plugin type path", plugin.name),
+                ));
+            }
+        }
+
+        // `TypeUuid` identifies asset types, copies of the default collide at runtime
+        let mut uuids = HashSet::new();
+        for (i, asset) in self.custom_assets.iter().enumerate() {
+            let path = format!("custom_assets[{i}]");
+            if !is_uuid(&asset.uuid) {
+                diagnostics.push(Diagnostic::error(
+                    path,
+                    format!("`{}` is not a UUID", asset.uuid),
+                ));
+            } else if !uuids.insert(asset.uuid.to_lowercase()) {
+                diagnostics.push(Diagnostic::error(
+                    path,
+                    format!("uuid `{}` is used by another asset", asset.uuid),
+                ));
+            }
+        }
+
+        let known = self
+            .components
+            .iter()
+            .map(|c| c.name.as_str())
+            .chain(BEVY_COMPONENTS)
+            .collect::<HashSet<&str>>();
+        for (kind, systems) in [
+            ("startup_systems", &self.startup_systems),
+            ("systems", &self.systems),
+        ] {
+            for (i, system) in systems.iter().enumerate() {
+                for component in referenced_components(system) {
+                    let base = component.split('<').next().unwrap_or_default().trim();
+                    if !known.contains(base) {
+                        diagnostics.push(Diagnostic::warning(
+                            format!("{kind}[{i}]"),
+                            format!(
+                                "`{}` queries `{component}`, which is not a component of this model",
+                                system.name
+                            ),
+                        ));
+                    }
+                }
+            }
+        }
+
+        // Children are generated as their own crates and files
+        for (kind, children) in [
+            ("plugin_crates", &self.plugin_crates),
+            ("examples", &self.examples),
+        ] {
+            for (i, child) in children.iter().enumerate() {
+                diagnostics.extend(child.validate().into_iter().map(|mut d| {
+                    d.path = format!("{kind}[{i}].{}", d.path);
+                    d
+                }));
+            }
+        }
+
+        diagnostics
+    }
+}
+
+fn names<T>(items: &[T], name: impl Fn(&T) -> &String) -> Vec<&str> {
+    items.iter().map(|i| name(i).as_str()).collect()
+}
+
+fn is_identifier(name: &str) -> bool {
+    syn::parse_str::<syn::Ident>(name).is_ok()
+}
+
+// Hyphenated form, e.g. `39cadc56-aa9c-4543-8640-a018b74b5052`
+fn is_uuid(uuid: &str) -> bool {
+    uuid.len() == 36
+        && uuid.char_indices().all(|(i, c)| match i {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        })
+}
+
+// Names must be unique across all kinds that end up in the same crate
+fn duplicates(kinds: &[(&str, Vec<&str>)]) -> Vec<Diagnostic> {
+    let mut seen = HashSet::new();
+    kinds
+        .iter()
+        .flat_map(|(kind, names)| names.iter().enumerate().map(move |(i, n)| (kind, i, n)))
+        .filter(|(_, _, name)| !seen.insert(**name))
+        .map(|(kind, i, name)| {
+            Diagnostic::error(
+                format!("{kind}[{i}]"),
+                format!("`{name}` is declared twice"),
+            )
+        })
+        .collect()
+}
+
+fn referenced_components(system: &System) -> Vec<&str> {
+    let mut components = system.reads();
+    components.extend(system.writes());
+    for param in &system.param {
+        if let SystemParam::Query { filter, .. } = param {
+            components.extend(filter.iter().filter_map(|f| match f {
+                QueryFilter::With(c)
+                | QueryFilter::Without(c)
+                | QueryFilter::Added(c)
+                | QueryFilter::Changed(c) => Some(c.as_str()),
+                QueryFilter::Custom(_) => None,
+            }));
+        }
+    }
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::{Component, CustomAsset, Plugin, QueryData};
+
+    use super::*;
+
+    #[test]
+    fn valid_model_has_no_diagnostics() {
+        let bm = BevyModel {
+            components: vec![Component {
+                name: "Player".to_string(),
+                ..Default::default()
+            }],
+            systems: vec![System {
+                name: "movement".to_string(),
+                param: vec![SystemParam::Query {
+                    name: "players".to_string(),
+                    data: vec![QueryData::Mut("Transform".to_string())],
+                    filter: vec![QueryFilter::With("Player".to_string())],
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert_eq!(Vec::<Diagnostic>::new(), bm.validate());
+    }
+
+    #[test]
+    fn children_are_validated() {
+        let child = BevyModel {
+            components: vec![Component {
+                name: "Enemy AI".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let bm = BevyModel {
+            plugin_crates: vec![child.clone()],
+            examples: vec![BevyModel {
+                plugin_crates: vec![child],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert_eq!(
+            vec![
+                "error: plugin_crates[0].components[0]: `Enemy AI` is not a valid Rust identifier",
+                "error: examples[0].plugin_crates[0].components[0]: `Enemy AI` is not a valid Rust identifier",
+            ],
+            bm.validate()
+                .iter()
+                .map(|d| d.to_string())
+                .collect::<Vec<String>>()
+        );
+    }
+
+    #[test]
+    fn broken_model_reports_diagnostics() {
+        let component = |name: &str| Component {
+            name: name.to_string(),
+            ..Default::default()
+        };
+        let bm = BevyModel {
+            components: vec![
+                component("Ship"),
+                component("Other-Ship"),
+                component("Ship"),
+            ],
+            plugins: vec![Plugin {
+                name: "EditorPlugin; std::process::exit(1)".to_string(),
+                ..Default::default()
+            }],
+            systems: vec![System {
+                name: "shoot".to_string(),
+                param: vec![SystemParam::Query {
+                    name: "guns".to_string(),
+                    data: vec![QueryData::Ref("Gun".to_string())],
+                    filter: vec![],
+                }],
+                ..Default::default()
+            }],
+            custom_assets: vec![
+                CustomAsset {
+                    name: "Level".to_string(),
+                    ..Default::default()
+                },
+                CustomAsset {
+                    name: "Dialogue".to_string(),
+                    ..Default::default()
+                },
+                CustomAsset {
+                    name: "Tileset".to_string(),
+                    uuid: "not-a-uuid".to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let diagnostics = bm.validate();
+        assert_eq!(
+            vec![
+                "error: components[1]: `Other-Ship` is not a valid Rust identifier",
+                "error: components[2]: `Ship` is declared twice",
+                "error: plugins[0]: `EditorPlugin; std::process::exit(1)` is not a plugin type path",
+                "error: custom_assets[1]: uuid `39cadc56-aa9c-4543-8640-a018b74b5052` is used by another asset",
+                "error: custom_assets[2]: `not-a-uuid` is not a UUID",
+                "warning: systems[0]: `shoot` queries `Gun`, which is not a component of this model",
+            ],
+            diagnostics
+                .iter()
+                .map(|d| d.to_string())
+                .collect::<Vec<String>>()
+        );
+    }
+}
@@ -158,7 +158,11 @@ impl EditorWindow for PotooClientEditorWindow {
                                     if ui.button("Edit").clicked() {
                                         ce.code = system.content.clone();
                                     }
-                                    _ = ui.label(system.name.as_str());
+                                    _ = ui.label(system.name.as_str()).on_hover_text(format!(
+                                        "Reads: {}\nWrites: {}",
+                                        system.reads().join(", "),
+                                        system.writes().join(", ")
+                                    ));
                                     _ = ui.button("X");
                                 });
                             }
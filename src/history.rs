@@ -1,4 +1,4 @@
-use bevy_codegen::model::{BevyModel, Component, System};
+use bevy_codegen::model::{BevyModel, Bundle, Component, Identified, ModelId, Resource, System};
 use undo::{Action, History};
 
 #[derive(Clone, Debug)]
@@ -6,19 +6,28 @@ use undo::{Action, History};
 pub enum PotooEvent {
     //Add
     AddComponent(Component),
+    AddBundle(Bundle),
+    AddResource(Resource),
     AddStartupSystem(System),
     AddRunTimeSystem(System),
     //AddComponentToEntity(Entity, Component),
 
     //Remove
     RemoveComponent(Component),
+    RemoveBundle(Bundle),
+    RemoveResource(Resource),
     RemoveStartupSystem(System),
     RemoveRunTimeSystem(System),
 
     //Update
     UpdateComponent(Component),
+    UpdateBundle(Bundle),
+    UpdateResource(Resource),
     UpdateStartupSystem(System),
     UpdateRunTimeSystem(System),
+
+    //Rename any element by its ID
+    Rename(ModelId, String),
 }
 
 pub enum ReloadType {
@@ -37,7 +46,18 @@ pub fn reload_get_type(event: PotooEvent) -> ReloadType {
 pub struct PotooEvents(pub PotooEvent);
 
 impl ProjectModel {
+    pub fn new(mut model: BevyModel) -> Self {
+        model.assign_ids();
+        Self {
+            model,
+            history: History::new(),
+        }
+    }
+
     pub fn apply(&mut self, event: PotooEvents) {
+        if let Some(warning) = self.removal_warning(&event.0) {
+            println!("Warning: {warning}");
+        }
         self.history.apply(&mut self.model, event.clone());
         match reload_get_type(event.clone().0) {
             ReloadType::RequireReload => println!("Reload required for action: {:?}", event.0),
@@ -45,6 +65,24 @@ impl ProjectModel {
         }
     }
 
+    //Components still used by systems leave them broken when removed
+    pub fn removal_warning(&self, event: &PotooEvent) -> Option<String> {
+        let PotooEvent::RemoveComponent(c) = event else {
+            return None;
+        };
+        let index = self.model.component_usage();
+        let usages = index.usages(&c.name);
+        if usages.is_empty() {
+            return None;
+        }
+        let systems = usages
+            .iter()
+            .map(|u| u.to_string())
+            .collect::<Vec<String>>()
+            .join(", ");
+        Some(format!("`{}` is still used by {systems}", c.name))
+    }
+
     pub fn redo(&mut self) {
         self.history.redo(&mut self.model);
     }
@@ -64,82 +102,45 @@ impl Action for PotooEvents {
     type Output = ();
 
     fn apply(&mut self, target: &mut Self::Target) -> Self::Output {
-        match &self.0 {
-            PotooEvent::AddComponent(c) => target.components.push(c.clone()),
-            PotooEvent::RemoveComponent(c) => target.components.retain(|x| x.name != c.name),
-            PotooEvent::AddStartupSystem(s) => target.startup_systems.push(s.clone()),
-            PotooEvent::AddRunTimeSystem(s) => target.systems.push(s.clone()),
-            PotooEvent::RemoveStartupSystem(s) => {
-                target.startup_systems.retain(|x| x.name != s.name)
-            }
-            PotooEvent::RemoveRunTimeSystem(s) => target.systems.retain(|x| x.name != s.name),
-            PotooEvent::UpdateComponent(c) => {
-                let index = target
-                    .components
-                    .iter()
-                    .position(|x| *x.name == c.name)
-                    .expect("Component with name found");
-                let _tmp = target.components.remove(index);
-                target.components.insert(index, c.clone());
-            }
-            PotooEvent::UpdateStartupSystem(s) => {
-                let index = target
-                    .startup_systems
-                    .iter()
-                    .position(|x| *x.name == s.name)
-                    .expect("Startup system with name found");
-                let _tmp = target.startup_systems.remove(index);
-                target.startup_systems.insert(index, s.clone());
-            }
-            PotooEvent::UpdateRunTimeSystem(s) => {
-                let index = target
-                    .systems
-                    .iter()
-                    .position(|x| *x.name == s.name)
-                    .expect("Runtime system with name found");
-                let _tmp = target.systems.remove(index);
-                target.systems.insert(index, s.clone());
-            }
+        match &mut self.0 {
+            PotooEvent::AddComponent(c) => add(target, c, |t| &mut t.components),
+            PotooEvent::RemoveComponent(c) => remove(&mut target.components, c.id),
+            PotooEvent::AddBundle(b) => add(target, b, |t| &mut t.bundles),
+            PotooEvent::RemoveBundle(b) => remove(&mut target.bundles, b.id),
+            PotooEvent::AddResource(r) => add(target, r, |t| &mut t.resources),
+            PotooEvent::RemoveResource(r) => remove(&mut target.resources, r.id),
+            PotooEvent::AddStartupSystem(s) => add(target, s, |t| &mut t.startup_systems),
+            PotooEvent::AddRunTimeSystem(s) => add(target, s, |t| &mut t.systems),
+            PotooEvent::RemoveStartupSystem(s) => remove(&mut target.startup_systems, s.id),
+            PotooEvent::RemoveRunTimeSystem(s) => remove(&mut target.systems, s.id),
+            PotooEvent::UpdateComponent(c) => swap(&mut target.components, c),
+            PotooEvent::UpdateBundle(b) => swap(&mut target.bundles, b),
+            PotooEvent::UpdateResource(r) => swap(&mut target.resources, r),
+            PotooEvent::UpdateStartupSystem(s) => swap(&mut target.startup_systems, s),
+            PotooEvent::UpdateRunTimeSystem(s) => swap(&mut target.systems, s),
+            PotooEvent::Rename(id, name) => rename(target, *id, name),
         };
     }
 
     fn undo(&mut self, target: &mut Self::Target) -> Self::Output {
-        match &self.0 {
-            PotooEvent::AddComponent(c) => target.components.retain(|x| x.name != c.name),
-            PotooEvent::RemoveComponent(c) => {
-                target.components.push(c.clone());
-            }
-            PotooEvent::AddStartupSystem(s) => target.startup_systems.retain(|x| x.name != s.name),
-            PotooEvent::AddRunTimeSystem(s) => target.systems.retain(|x| x.name != s.name),
+        match &mut self.0 {
+            PotooEvent::AddComponent(c) => remove(&mut target.components, c.id),
+            PotooEvent::RemoveComponent(c) => target.components.push(c.clone()),
+            PotooEvent::AddBundle(b) => remove(&mut target.bundles, b.id),
+            PotooEvent::RemoveBundle(b) => target.bundles.push(b.clone()),
+            PotooEvent::AddResource(r) => remove(&mut target.resources, r.id),
+            PotooEvent::RemoveResource(r) => target.resources.push(r.clone()),
+            PotooEvent::AddStartupSystem(s) => remove(&mut target.startup_systems, s.id),
+            PotooEvent::AddRunTimeSystem(s) => remove(&mut target.systems, s.id),
             PotooEvent::RemoveStartupSystem(s) => target.startup_systems.push(s.clone()),
             PotooEvent::RemoveRunTimeSystem(s) => target.systems.push(s.clone()),
-            PotooEvent::UpdateComponent(c) => {
-                let index = target
-                    .components
-                    .iter()
-                    .position(|x| *x.name == c.name)
-                    .expect("Component with name found");
-                let _tmp = target.components.remove(index);
-                target.components.insert(index, c.clone());
-            }
-            PotooEvent::UpdateStartupSystem(s) => {
-                let index = target
-                    .startup_systems
-                    .iter()
-                    .position(|x| *x.name == s.name)
-                    .expect("Startup system with name found");
-                let _tmp = target.startup_systems.remove(index);
-                target.startup_systems.insert(index, s.clone());
-            }
-            PotooEvent::UpdateRunTimeSystem(s) => {
-                let index = target
-                    .systems
-                    .iter()
-                    .position(|x| *x.name == s.name)
-                    .expect("Runtime system with name found");
-                let _tmp = target.systems.remove(index);
-                target.systems.insert(index, s.clone());
-            }
+            //Updates and renames swap the stored value back
+            PotooEvent::UpdateComponent(c) => swap(&mut target.components, c),
+            PotooEvent::UpdateBundle(b) => swap(&mut target.bundles, b),
+            PotooEvent::UpdateResource(r) => swap(&mut target.resources, r),
+            PotooEvent::UpdateStartupSystem(s) => swap(&mut target.startup_systems, s),
+            PotooEvent::UpdateRunTimeSystem(s) => swap(&mut target.systems, s),
+            PotooEvent::Rename(id, name) => rename(target, *id, name),
         };
     }
 
@@ -148,8 +149,127 @@ impl Action for PotooEvents {
     }
 }
 
+//New elements get their ID on first apply, redo keeps it
+fn add<T: Identified + Clone>(
+    target: &mut BevyModel,
+    item: &mut T,
+    items: impl Fn(&mut BevyModel) -> &mut Vec<T>,
+) {
+    if !item.id().is_assigned() {
+        item.set_id(target.new_id());
+    }
+    items(target).push(item.clone());
+}
+
+fn remove<T: Identified>(items: &mut Vec<T>, id: ModelId) {
+    items.retain(|x| x.id() != id)
+}
+
+//Exchanges the stored element with the one in the model
+fn swap<T: Identified>(items: &mut [T], item: &mut T) {
+    let element = items
+        .iter_mut()
+        .find(|x| x.id() == item.id())
+        .expect("Element with id found");
+    std::mem::swap(element, item);
+}
+
+fn rename(target: &mut BevyModel, id: ModelId, name: &mut String) {
+    let element = target
+        .identified_mut()
+        .find(|x| x.id() == id)
+        .expect("Element with id found");
+    std::mem::swap(element.name_mut(), name);
+}
+
 #[derive(Debug)]
 pub struct ProjectModel {
     pub model: BevyModel,
     pub history: History<PotooEvents>,
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_codegen::model::{QueryData, SystemParam};
+
+    use super::*;
+
+    fn component(name: &str) -> Component {
+        Component {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn rename_keeps_history_intact() {
+        let mut pm = ProjectModel::new(BevyModel::default());
+        pm.apply(PotooEvents(PotooEvent::AddComponent(component("Ship"))));
+        let id = pm.model.components[0].id;
+        pm.apply(PotooEvents(PotooEvent::Rename(id, "Player".to_string())));
+        assert_eq!("Player", pm.model.components[0].name);
+        assert_eq!(id, pm.model.components[0].id);
+
+        pm.undo();
+        assert_eq!("Ship", pm.model.components[0].name);
+        pm.redo();
+        assert_eq!("Player", pm.model.components[0].name);
+        pm.undo();
+        pm.undo();
+        assert!(pm.model.components.is_empty());
+    }
+
+    #[test]
+    fn same_names_are_kept_apart() {
+        let mut pm = ProjectModel::new(BevyModel::default());
+        pm.apply(PotooEvents(PotooEvent::AddComponent(component("Ship"))));
+        pm.apply(PotooEvents(PotooEvent::AddComponent(component("Ship"))));
+        let first = pm.model.components[0].clone();
+        assert_ne!(first.id, pm.model.components[1].id);
+
+        pm.apply(PotooEvents(PotooEvent::RemoveComponent(first.clone())));
+        assert_eq!(1, pm.model.components.len());
+        assert_ne!(first.id, pm.model.components[0].id);
+        pm.undo();
+        assert_eq!(2, pm.model.components.len());
+    }
+
+    #[test]
+    fn undo_update_restores_previous_element() {
+        let mut pm = ProjectModel::new(BevyModel::default());
+        pm.apply(PotooEvents(PotooEvent::AddComponent(component("Ship"))));
+        let updated = Component {
+            is_reflected: false,
+            ..pm.model.components[0].clone()
+        };
+        pm.apply(PotooEvents(PotooEvent::UpdateComponent(updated)));
+        assert!(!pm.model.components[0].is_reflected);
+        pm.undo();
+        assert!(pm.model.components[0].is_reflected);
+        pm.redo();
+        assert!(!pm.model.components[0].is_reflected);
+    }
+
+    #[test]
+    fn removing_used_component_warns() {
+        let mut pm = ProjectModel::new(BevyModel::default());
+        pm.apply(PotooEvents(PotooEvent::AddComponent(component("Ship"))));
+        pm.apply(PotooEvents(PotooEvent::AddRunTimeSystem(System {
+            name: "fly".to_string(),
+            param: vec![SystemParam::Query {
+                name: "ships".to_string(),
+                data: vec![QueryData::Mut("Ship".to_string())],
+                filter: vec![],
+            }],
+            ..Default::default()
+        })));
+        let ship = PotooEvent::RemoveComponent(pm.model.components[0].clone());
+        assert_eq!(
+            Some("`Ship` is still used by fly (writes)".to_string()),
+            pm.removal_warning(&ship)
+        );
+
+        pm.undo();
+        assert_eq!(None, pm.removal_warning(&ship));
+    }
+}
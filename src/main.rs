@@ -2,6 +2,7 @@ pub mod history;
 pub mod templates;
 
 use std::{
+    error::Error,
     fs::{self, File},
     io::Write,
     path::Path,
@@ -15,8 +16,10 @@ use bevy::{
 */
 use bevy_codegen::{
     generate::GenerationType,
-    model::{BevyModel, Component, ConfirmPo2Version, Po2Version, ReadPo2Version},
+    model::{migrate_po2, BevyModel, Component, ConfirmPo2Version, Po2Version, ReadPo2Version},
+    schema::{po2_schema_json, validate_po2_json},
     templates::default_cargo_src_template,
+    validate::{Diagnostic, Severity},
 };
 //use bevy_editor_pls::prelude::*;
 use codegen::Scope;
@@ -24,9 +27,54 @@ use history::PotooEvent::*;
 use history::{PotooEvents, ProjectModel};
 use rust_format::{Formatter, RustFmt};
 use templates::default_game_template;
-use undo::History;
 
 fn main() {
+    //.po2.json tooling, see README
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    match args
+        .iter()
+        .map(|a| a.as_str())
+        .collect::<Vec<&str>>()
+        .as_slice()
+    {
+        ["merge", base, ours, theirs] => {
+            //git treats any non-zero code as a conflict, 2 marks a failed merge
+            let code = match merge_po2_files(base, ours, theirs) {
+                Ok(true) => 0,
+                Ok(false) => 1,
+                Err(e) => {
+                    eprintln!("Merge failed: {e}");
+                    2
+                }
+            };
+            std::process::exit(code);
+        }
+        ["schema"] => {
+            print!("{}", po2_schema_json());
+            return;
+        }
+        ["validate", path] => {
+            let code = match validate_po2_file(path) {
+                Ok(diagnostics) => {
+                    for diagnostic in &diagnostics {
+                        println!("{diagnostic}");
+                    }
+                    if diagnostics.is_empty() {
+                        0
+                    } else {
+                        1
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Validation failed: {e}");
+                    2
+                }
+            };
+            std::process::exit(code);
+        }
+        _ => (),
+    }
+
     /*
     let run_app = false;
     if run_app {
@@ -39,10 +87,7 @@ fn main() {
     }
     */
     let bm = default_game_template();
-    let mut pm = ProjectModel {
-        model: bm,
-        history: History::new(),
-    };
+    let mut pm = ProjectModel::new(bm);
 
     pm.apply(PotooEvents(AddComponent(Component {
         name: "OtherShip".to_string(),
@@ -68,6 +113,14 @@ fn main() {
         println!("{}\n", pm.model);
     }
 
+    let diagnostics = pm.model.validate();
+    for diagnostic in &diagnostics {
+        println!("{diagnostic}");
+    }
+    if diagnostics.iter().any(|d| d.severity == Severity::Error) {
+        return;
+    }
+
     //Write to file
     let bevy_folder = pm.model.meta.name.clone();
     let already_exists = Path::new(&bevy_folder).exists();
@@ -81,6 +134,10 @@ fn main() {
     //Remove whole project
     //let res = fs::remove_dir_all(bevy_folder.to_owned());
 
+    for missing in pm.model.missing_assets() {
+        println!("Warning: {missing}");
+    }
+
     let _ = pm.model.generate(GenerationType::Main);
     let _ = pm.model.generate(GenerationType::Components);
     let _ = pm.model.generate(GenerationType::Systems);
@@ -157,23 +214,57 @@ fn main() {
 
 #[must_use]
 fn read_po2_file(path: String) -> BevyModel {
-    let file_as_string = fs::read_to_string(path).unwrap();
-    let parsed_version_file = serde_json::from_str::<ReadPo2Version>(&file_as_string).unwrap();
-    let compatible = match parsed_version_file.po2_version {
-        Po2Version::V0_0_1 => true,
-        parsed_po2_version => {
-            println!(
-                "Warning: File is not compatible ({parsed_po2_version}) with current version {}",
-                Po2Version::default()
-            );
-            false
-        }
+    parse_po2_file(&path).unwrap()
+}
+
+fn parse_po2_file(path: &str) -> Result<BevyModel, Box<dyn Error>> {
+    let mut file_as_string = fs::read_to_string(path)?;
+    let mut raw_file = serde_json::from_str::<serde_json::Value>(&file_as_string)?;
+    //Older files are converted and written back at the current version
+    if let Some(written) = migrate_po2(&mut raw_file)? {
+        println!(
+            "Migrated {path} from version {written} to {}",
+            Po2Version::default()
+        );
+        file_as_string = serde_json::to_string(&raw_file)?;
+        let _ = fs::write(path, &file_as_string);
+    }
+    for diagnostic in validate_po2_json(&file_as_string) {
+        println!("Warning: {diagnostic}");
+    }
+    let parsed_file = serde_json::from_str::<ConfirmPo2Version>(&file_as_string)?;
+    Ok(parsed_file.model)
+}
+
+//Older files are checked as migrated to the current version, the file itself is not changed
+fn validate_po2_file(path: &str) -> Result<Vec<Diagnostic>, Box<dyn Error>> {
+    let mut raw_file = serde_json::from_str::<serde_json::Value>(&fs::read_to_string(path)?)?;
+    if let Some(written) = migrate_po2(&mut raw_file)? {
+        println!(
+            "{path} is version {written}, validating it migrated to {}",
+            Po2Version::default()
+        );
+    }
+    Ok(validate_po2_json(&serde_json::to_string(&raw_file)?))
+}
+
+//Writes the merged model to `ours`, conflicting elements keep our version
+fn merge_po2_files(base: &str, ours: &str, theirs: &str) -> Result<bool, Box<dyn Error>> {
+    //git passes an empty base when both sides added the file
+    let base = match fs::read_to_string(base) {
+        Ok(content) if !content.trim().is_empty() => parse_po2_file(base)?,
+        _ => BevyModel::default(),
     };
-    if !compatible {
-        //TODO: Do something
+    let result = BevyModel::merge(&base, &parse_po2_file(ours)?, &parse_po2_file(theirs)?);
+    for conflict in &result.conflicts {
+        eprintln!("Conflict: {conflict}");
     }
-    let parsed_file = serde_json::from_str::<ConfirmPo2Version>(&file_as_string).unwrap();
-    parsed_file.model
+    let ser_prep = ReadPo2Version {
+        po2_version: Po2Version::default(),
+        model: result.model,
+    };
+    fs::write(ours, serde_json::to_string(&ser_prep)?)?;
+    Ok(result.conflicts.is_empty())
 }
 
 fn remove_path(path: String) {
@@ -1,6 +1,7 @@
 use bevy_codegen::model::{
-    BevyModel, BevyType, CargoDependency, Component, Custom, CustomCode, DependencyType, Feature,
-    Import, Meta, Plugin, System, Used,
+    BevyModel, BevyType, Bundle, CargoDependency, Component, ComponentField, Custom, CustomCode,
+    DependencyType, Feature, Import, Meta, Plugin, PluginMember, QueryData, QueryFilter, System,
+    SystemParam, Used,
 };
 
 pub fn default_game_template() -> BevyModel {
@@ -16,18 +17,42 @@ pub fn default_game_template() -> BevyModel {
     bevy_model.components.push(Component {
         name: "Player".to_string(),
         content: vec![
-            ("velocity".to_string(), "Vec3".to_string()),
-            ("rotation_speed".to_string(), "f32".to_string()),
-            ("shooting_timer".to_string(), "Option<f32>".to_string()),
+            ComponentField {
+                name: "velocity".to_string(),
+                ty: "Vec3".to_string(),
+                default: Some("Vec3::ZERO".to_string()),
+            },
+            ComponentField {
+                name: "rotation_speed".to_string(),
+                ty: "f32".to_string(),
+                default: Some("f32::to_radians(180.0)".to_string()),
+            },
+            ComponentField {
+                name: "shooting_timer".to_string(),
+                ty: "Option<f32>".to_string(),
+                default: None,
+            },
         ],
         ..Default::default()
     });
 
+    bevy_model.bundles.push(Bundle {
+        name: "PlayerBundle".to_string(),
+        components: vec!["Player".to_string(), "SpriteBundle".to_string()],
+        ..Default::default()
+    });
+
     //For asset_server
     bevy_model.plugins.push(Plugin {
-        name: "DefaultPlugins.set(AssetPlugin{watch_for_changes: true, ..default()})".to_string(),
+        id: Default::default(),
+        name: "DefaultPlugins".to_string(),
         is_group: true,
         dependencies: vec![],
+        members: vec![PluginMember {
+            name: "AssetPlugin".to_string(),
+            enabled: true,
+            config: Some("AssetPlugin{watch_for_changes: true, ..default()}".to_string()),
+        }],
     });
 
     let use_editor_pls = false;
@@ -48,17 +73,24 @@ pub fn default_game_template() -> BevyModel {
             dependency: cd.clone(),
         });
         bevy_model.plugins.push(Plugin {
+            id: Default::default(),
             name: "EditorPlugin".to_string(),
             is_group: false,
             dependencies: vec![cd],
+            members: vec![],
         });
     }
 
     let setup_entities = System {
         name: "setup".to_string(),
         param: vec![
-            ("mut commands".to_string(), "Commands".to_string()),
-            ("asset_server".to_string(), "Res<AssetServer>".to_string()),
+            SystemParam::Commands {
+                name: "commands".to_string(),
+            },
+            SystemParam::Res {
+                name: "asset_server".to_string(),
+                ty: "AssetServer".to_string(),
+            },
         ],
         content: r#"
 
@@ -66,15 +98,16 @@ commands.spawn(Camera2dBundle::default());
 
 // player
 let ship_handle = asset_server.load("ship_C.png");
-commands
-.spawn(SpriteBundle {
-    texture: ship_handle,
-    ..default()
-})
-.insert(Player {
-    velocity: Vec3::ZERO,
-    rotation_speed: f32::to_radians(180.0),
-    shooting_timer: None,
+commands.spawn(PlayerBundle {
+    player: Player {
+        velocity: Vec3::ZERO,
+        rotation_speed: f32::to_radians(180.0),
+        shooting_timer: None,
+    },
+    sprite_bundle: SpriteBundle {
+        texture: ship_handle,
+        ..default()
+    },
 });
 
 "#
@@ -87,10 +120,10 @@ commands
 
     bevy_model.systems.push(System {
         name: "contact_http".to_string(),
-        param: vec![(
-            "keyboard_input".to_string(),
-            "Res<Input<KeyCode>>".to_string(),
-        )],
+        param: vec![SystemParam::Res {
+            name: "keyboard_input".to_string(),
+            ty: "Input<KeyCode>".to_string(),
+        }],
         content: r#"if keyboard_input.just_pressed(KeyCode::H) {
 println!("Hello World");
 //Send request to server
@@ -103,15 +136,22 @@ println!("Hello World");
     bevy_model.systems.push(System {
         name: "player_movement_system".to_string(),
         param: vec![
-            (
-                "keyboard_input".to_string(),
-                "Res<Input<KeyCode>>".to_string(),
-            ),
-            (
-                "mut query".to_string(),
-                "Query<(&Player, &mut Transform)>".to_string(),
-            ),
-            ("time".to_string(), "Res<Time>".to_string()),
+            SystemParam::Res {
+                name: "keyboard_input".to_string(),
+                ty: "Input<KeyCode>".to_string(),
+            },
+            SystemParam::Query {
+                name: "query".to_string(),
+                data: vec![
+                    QueryData::Ref("Player".to_string()),
+                    QueryData::Mut("Transform".to_string()),
+                ],
+                filter: vec![],
+            },
+            SystemParam::Res {
+                name: "time".to_string(),
+                ty: "Time".to_string(),
+            },
         ],
         content: r#"const SPEED: f32 = 500.0;
 
@@ -154,15 +194,18 @@ transform.translation = transform.translation.min(extents).max(-extents);"#
     bevy_model.systems.push(System {
         name: "player_shooting_system".to_string(),
         param: vec![
-            ("mut commands".to_string(), "Commands".to_string()),
-            (
-                "keyboard_input".to_string(),
-                "Res<Input<KeyCode>>".to_string(),
-            ),
-            (
-                "query".to_string(),
-                "Query<&Transform, With<Player>>".to_string(),
-            ),
+            SystemParam::Commands {
+                name: "commands".to_string(),
+            },
+            SystemParam::Res {
+                name: "keyboard_input".to_string(),
+                ty: "Input<KeyCode>".to_string(),
+            },
+            SystemParam::Query {
+                name: "query".to_string(),
+                data: vec![QueryData::Ref("Transform".to_string())],
+                filter: vec![QueryFilter::With("Player".to_string())],
+            },
         ],
         content: r#"const SIZE: f32 = 10.0;
 
@@ -188,13 +231,23 @@ if keyboard_input.just_pressed(KeyCode::Space) {
     bevy_model.systems.push(System {
         name: "bullet_movement_system".to_string(),
         param: vec![
-            ("mut commands".to_string(), "Commands".to_string()),
-            (
-                "mut query".to_string(),
-                "Query<(Entity, &mut Transform), With<Bullet>>".to_string(),
-            ),
-            ("cam".to_string(), "Query<&Camera>".to_string()),
-            ("time".to_string(), "Res<Time>".to_string()),
+            SystemParam::Commands {
+                name: "commands".to_string(),
+            },
+            SystemParam::Query {
+                name: "query".to_string(),
+                data: vec![QueryData::Entity, QueryData::Mut("Transform".to_string())],
+                filter: vec![QueryFilter::With("Bullet".to_string())],
+            },
+            SystemParam::Query {
+                name: "cam".to_string(),
+                data: vec![QueryData::Ref("Camera".to_string())],
+                filter: vec![],
+            },
+            SystemParam::Res {
+                name: "time".to_string(),
+                ty: "Time".to_string(),
+            },
         ],
         content: r#"let screen_size = cam.single().logical_viewport_size().unwrap() * 0.5;
 let speed = 500.0;
@@ -224,15 +277,19 @@ for (entity, mut tfm) in &mut query {
     bevy_model.systems.push(System {
         name: "bullet_hit_system".to_string(),
         param: vec![
-            ("mut commands".to_string(), "Commands".to_string()),
-            (
-                "bullet_query".to_string(),
-                "Query<&Transform, With<Bullet>>".to_string(),
-            ),
-            (
-                "ship_query".to_string(),
-                "Query<(Entity, &Transform), With<OtherShip>>".to_string(),
-            ),
+            SystemParam::Commands {
+                name: "commands".to_string(),
+            },
+            SystemParam::Query {
+                name: "bullet_query".to_string(),
+                data: vec![QueryData::Ref("Transform".to_string())],
+                filter: vec![QueryFilter::With("Bullet".to_string())],
+            },
+            SystemParam::Query {
+                name: "ship_query".to_string(),
+                data: vec![QueryData::Entity, QueryData::Ref("Transform".to_string())],
+                filter: vec![QueryFilter::With("OtherShip".to_string())],
+            },
         ],
         content: r#"for bullet_tfm in bullet_query.iter() {
 for (entity, ship_tfm) in ship_query.iter() {
@@ -256,13 +313,23 @@ for (entity, ship_tfm) in ship_query.iter() {
     bevy_model.systems.push(System {
         name: "spawn_other_ships".to_string(),
         param: vec![
-            ("mut commands".to_string(), "Commands".to_string()),
-            ("asset_server".to_string(), "Res<AssetServer>".to_string()),
-            (
-                "others".to_string(),
-                "Query<(Entity, &Transform), With<OtherShip>>".to_string(),
-            ),
-            ("cam".to_string(), "Query<&Camera>".to_string()),
+            SystemParam::Commands {
+                name: "commands".to_string(),
+            },
+            SystemParam::Res {
+                name: "asset_server".to_string(),
+                ty: "AssetServer".to_string(),
+            },
+            SystemParam::Query {
+                name: "others".to_string(),
+                data: vec![QueryData::Entity, QueryData::Ref("Transform".to_string())],
+                filter: vec![QueryFilter::With("OtherShip".to_string())],
+            },
+            SystemParam::Query {
+                name: "cam".to_string(),
+                data: vec![QueryData::Ref("Camera".to_string())],
+                filter: vec![],
+            },
         ],
         content: r#"const MARGIN: f32 = 30.0;
 const MIN_SHIP_COUNT: usize = 10;
@@ -315,11 +382,15 @@ if other_ships_count < MIN_SHIP_COUNT {
     bevy_model.systems.push(System {
         name: "move_other_ships".to_string(),
         param: vec![
-            ("time".to_string(), "Res<Time>".to_string()),
-            (
-                "mut query".to_string(),
-                "Query<&mut Transform, With<OtherShip>>".to_string(),
-            ),
+            SystemParam::Res {
+                name: "time".to_string(),
+                ty: "Time".to_string(),
+            },
+            SystemParam::Query {
+                name: "query".to_string(),
+                data: vec![QueryData::Mut("Transform".to_string())],
+                filter: vec![QueryFilter::With("OtherShip".to_string())],
+            },
         ],
         content: r#"const SPEED: f32 = 100.0;
 for mut tfm in &mut query {
@@ -334,6 +405,7 @@ for mut tfm in &mut query {
     });
 
     bevy_model.custom.push(Custom::System(CustomCode {
+        id: Default::default(),
         name: "utilities.rs".to_string(),
         content: r#"use bevy::prelude::*;
 